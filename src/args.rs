@@ -1,11 +1,21 @@
-use crate::tournament::format;
+use crate::{
+    rating,
+    tournament::{
+        class_cup, division, doubles, draw, format, ladder, simulation::Simulation, svg::Paper,
+        team,
+    },
+};
 use std::path::PathBuf;
 
-#[derive(clap::Parser, Clone, Debug, PartialEq, Eq)]
+#[derive(clap::Parser, Clone, Debug, PartialEq)]
 #[command(version, about, long_about)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
     /// Path to file with participants: '<player/team>,<class>' syntax, where <class> is optional
-    pub file: PathBuf,
+    #[arg(required = true)]
+    pub file: Option<PathBuf>,
     /// Format in which the Tournament shall be carried out
     #[arg(short, long, value_enum, default_value_t = format::Supported::DoubleElimination)]
     pub format: format::Supported,
@@ -13,6 +23,160 @@ pub struct Args {
     /// NOTE: ignored if <format> is not elimination type
     #[arg(short, long, value_enum, default_value_t = Shuffle::Initially)]
     pub shuffle: Shuffle,
+    /// Save the log of played matches to this csv file after every round
+    #[arg(short, long)]
+    pub log: Option<PathBuf>,
+    /// Save the crosstable to this file after every round: a markdown table if it ends with '.md', csv otherwise
+    /// NOTE: ignored if <format> is elimination type
+    #[arg(short, long)]
+    pub crosstable: Option<PathBuf>,
+    /// Rank the classes by the final places of their players: points for the places, the first one for the winner
+    #[arg(long, value_name = "POINTS", num_args = 0..=1, default_missing_value = "10,8,6,5,4,3,2,1")]
+    pub class_cup: Option<class_cup::Points>,
+    /// Save the class cup to this file after every round: a markdown table if it ends with '.md', csv otherwise
+    /// NOTE: ignored without <CLASS_CUP>
+    #[arg(long)]
+    pub class_cup_file: Option<PathBuf>,
+    /// Keep a self-contained html page with the bracket or crosstable and standings at this path,
+    /// regenerated after every result
+    #[arg(long)]
+    pub html: Option<PathBuf>,
+    /// Keep a printable svg of the brackets filled in with the results at this path,
+    /// regenerated after every result
+    /// NOTE: ignored if <format> is not elimination type
+    #[arg(long)]
+    pub svg: Option<PathBuf>,
+    /// Write an empty printable svg of the brackets to this path: only the first round is filled in
+    /// NOTE: ignored if <format> is not elimination type
+    #[arg(long)]
+    pub svg_empty: Option<PathBuf>,
+    /// Paper the svg is made for
+    #[arg(long, value_enum, default_value_t = Paper::A4)]
+    pub paper: Paper,
+    /// Make the svg for landscape paper, portrait otherwise
+    #[arg(long)]
+    pub landscape: bool,
+    /// Update the ratings kept in this csv file with the played matches at the end of the Tournament
+    #[arg(long, num_args = 0..=1, default_missing_value = "ratings.csv")]
+    pub ratings: Option<PathBuf>,
+    /// Seed players by their ratings instead of shuffling them:
+    /// the best ones meet as late as possible in elimination formats, and in the swiss-system they're paired by it
+    /// NOTE: ratings are read from <RATINGS> or 'ratings.csv' if not given
+    #[arg(long)]
+    pub seed: bool,
+    #[command(flatten)]
+    pub rating: rating::Config,
+    /// Play the whole Tournament without prompts, choosing winners uniformly,
+    /// or by win probability from the 'rating' column of <FILE>
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "uniform")]
+    pub simulate: Option<Simulation>,
+    /// Seed of the random number generator: the same seed gives the same shuffles and simulated results
+    #[arg(long)]
+    pub random_seed: Option<u64>,
+    /// Split the participants into divisions playing independent Tournaments one after the other:
+    /// by the 'division' column of <FILE> with 'column', or by grade ranges, eg: '5-8,9-12'
+    #[arg(long)]
+    pub divisions: Option<division::Rule>,
+    /// Format of a division, <FORMAT> by default, eg: '5-8=round-robin', may be given for every division
+    #[arg(long, value_name = "DIVISION=FORMAT", value_parser = division::parse_format)]
+    pub division_format: Vec<(String, format::Supported)>,
+    /// Doubles: participants are pairs of the players of <FILE> made up by their 'partner' and 'partner_class' columns,
+    /// randomly, or balanced by their ratings: the best with the worst one
+    /// NOTE: ratings are read from <RATINGS> or 'ratings.csv' if not given
+    #[arg(long, value_enum, value_name = "PAIRING", num_args = 0..=1, default_missing_value = "partner")]
+    pub doubles: Option<doubles::Pairing>,
+    /// Draw teams of this size from the players of <FILE> to be the participants: balanced by <BALANCE>,
+    /// with players of different classes together as far as possible
+    #[arg(long, value_name = "SIZE", value_parser = clap::value_parser!(u8).range(2..), conflicts_with = "doubles")]
+    pub draw_teams: Option<u8>,
+    /// What drawn teams are balanced by
+    /// NOTE: ratings are read from <RATINGS> or 'ratings.csv' if not given
+    #[arg(long, value_enum, default_value_t = draw::Balance::Rating)]
+    pub balance: draw::Balance,
+    /// Run heats of at most this many participants instead of duels: the best <ADVANCE> of every heat go on
+    /// to the next round, till all of them fit into the final one
+    /// NOTE: <FORMAT> is ignored
+    #[arg(long, value_name = "SIZE", value_parser = clap::value_parser!(u8).range(2..),
+        conflicts_with_all = ["divisions", "doubles", "draw_teams", "tie"])]
+    pub heats: Option<u8>,
+    /// Number of participants going on from a heat to the next round
+    #[arg(long, default_value_t = 1)]
+    pub advance: u8,
+    /// Participants are teams playing ties made up of these rubbers, the first team to win most of them wins:
+    /// 'swaythling', 'corbillon' or rubbers like 'AX,BY,D,AY,BX', where 'A', 'B', 'C' are the home players,
+    /// 'X', 'Y', 'Z' the away ones, and 'D' is the doubles
+    #[arg(long)]
+    pub tie: Option<team::TieFormat>,
+    /// Members of the teams in a csv file with 'team' and 'name' columns, lineups of ties are checked against it
+    #[arg(long)]
+    pub rosters: Option<PathBuf>,
+    /// Check in the participants before the Tournament starts: mark the present ones, add walk-ins,
+    /// the no-shows are removed
+    /// NOTE: ignored if simulated
+    #[arg(long)]
+    pub check_in: bool,
+    /// Refuse to start if there's any problem with the file with participants, see the 'check' command
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(clap::Subcommand, Clone, Debug, PartialEq)]
+pub enum Command {
+    /// List the current ranking of the ratings
+    Ratings {
+        /// Path to the csv file the ratings are kept in
+        #[arg(default_value = "ratings.csv")]
+        file: PathBuf,
+        #[command(flatten)]
+        rating: rating::Config,
+    },
+    /// Check the file with participants: invalid classes, empty names, duplicate players, unknown columns
+    Check {
+        /// Path to file with participants
+        file: PathBuf,
+    },
+    /// Simulate every format many times and compare how fair they are for the participants
+    Analyze {
+        /// Path to file with participants and their strength in a 'rating' column
+        file: PathBuf,
+        /// Take the strength of participants from the ratings kept in this csv file instead
+        #[arg(long)]
+        ratings: Option<PathBuf>,
+        /// Number of simulated runs of every format
+        #[arg(short, long, default_value_t = 1000)]
+        runs: usize,
+        /// Seed of the random number generator: the same seed gives the same analysis
+        #[arg(long)]
+        random_seed: Option<u64>,
+    },
+    /// Estimate the duration of every format and recommend one that fits in the available time
+    Plan {
+        /// Path to file with participants
+        file: PathBuf,
+        /// Number of tables (courts, boards) matches can be played at the same time
+        #[arg(short, long, default_value_t = 1)]
+        tables: usize,
+        /// Average duration of a match in minutes
+        #[arg(short = 'd', long, default_value_t = 10)]
+        match_duration: usize,
+        /// Available time in minutes
+        #[arg(long)]
+        time: Option<usize>,
+    },
+    /// Run an ongoing ladder: players challenge others above them and swap places on a win
+    Ladder {
+        /// Path to the csv file the ladder is kept in
+        #[arg(default_value = "ladder.csv")]
+        file: PathBuf,
+        /// Path to the csv file the history of the ladder is kept in
+        #[arg(long, default_value = "ladder-history.csv")]
+        history: PathBuf,
+        /// Path to file with participants joining at the bottom, the ones already on the ladder are skipped
+        #[arg(long)]
+        join: Option<PathBuf>,
+        #[command(flatten)]
+        rules: ladder::Rules,
+    },
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
@@ -0,0 +1,10 @@
+//! # Tuna Man: tournament manager
+//!
+//! create and manage tournaments of [`tournament::Players`] in a [`tournament::format::Format`]
+
+/// argument parsing
+pub mod args;
+/// ratings of players kept across tournaments
+pub mod rating;
+/// the tournament itself: logic/backend
+pub mod tournament;
@@ -1,29 +1,56 @@
-use args::Args;
 use clap::Parser;
-use tournament::{format, Tournament};
+use tuna_man::{
+    args::{Args, Command},
+    rating::Ratings,
+    tournament::{self, analysis, division, ladder, planner, Players, TournamentError},
+};
 
-/// argument parsing
-mod args;
-/// the tournament itself: logic/backend
-mod tournament;
-
-fn main() -> std::io::Result<()> {
-    let args = Args::parse();
-    // let format = args.format.to_format();
-    match args.format {
-        format::Supported::SingleElimination => {
-            Tournament::new(format::SingleElimination::default()).execute(args)
-        }
-        format::Supported::DoubleElimination => {
-            Tournament::new(format::DoubleElimination::default()).execute(args)
+fn main() {
+    match run(Args::parse()) {
+        // everything's been saved already
+        Ok(()) | Err(TournamentError::Quit) => {}
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
         }
-        format::Supported::RoundRobin => {
-            Tournament::new(format::RoundRobin::default()).execute(args)
+    }
+}
+
+fn run(args: Args) -> Result<(), TournamentError> {
+    match &args.command {
+        Some(Command::Ratings { file, rating }) => {
+            Ratings::load(file)?.print(rating);
+            return Ok(());
         }
-        format::Supported::SwissSystem => {
-            Tournament::new(format::SwissSystem::default()).execute(args)
+        Some(Command::Check { file }) => {
+            let players = Players::load_strict(file)?;
+            println!("{} participants, no problems found", players.0.len());
+            return Ok(());
         }
+        Some(Command::Analyze {
+            file,
+            ratings,
+            runs,
+            random_seed,
+        }) => return analysis::execute(file, ratings.as_deref(), *runs, *random_seed),
+        Some(Command::Plan {
+            file,
+            tables,
+            match_duration,
+            time,
+        }) => return planner::execute(file, *tables, *match_duration, *time),
+        Some(Command::Ladder {
+            file,
+            history,
+            join,
+            rules,
+        }) => return ladder::execute(file, history, join.as_deref(), *rules),
+        None => {}
+    }
+    if let Some(rule) = args.divisions.clone() {
+        return division::execute(args, &rule);
     }
+    tournament::execute(args)
 
     // TODO: ratatui ui
     // let mut terminal = ratatui::try_init()?;
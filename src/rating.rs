@@ -0,0 +1,334 @@
+use crate::tournament::{Class, Duel, Player};
+pub use glicko2::Glicko2;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+/// [Glicko-2](https://en.wikipedia.org/wiki/Glicko_rating_system#Glicko-2_algorithm) rating system
+pub mod glicko2;
+
+/// default of [`Glicko2::initial_deviation`]
+const INITIAL_DEVIATION: f64 = 350.;
+/// default of [`Glicko2::initial_volatility`]
+const INITIAL_VOLATILITY: f64 = 0.06;
+
+/// the rating of a [`Player`] kept across tournaments
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    /// name of the [`Player`]: they're identified by it, as their class changes by years
+    pub name: String,
+    /// last known class of the [`Player`]
+    pub class: Option<Class>,
+    pub rating: f64,
+    /// rating deviation: uncertainty of `rating`, only used by [`Glicko2`]
+    #[serde(default = "initial_deviation")]
+    pub deviation: f64,
+    /// the degree of expected fluctuation in `rating`, only used by [`Glicko2`]
+    #[serde(default = "initial_volatility")]
+    pub volatility: f64,
+    /// number of [`Duel`]s rated so far
+    pub games: usize,
+}
+fn initial_deviation() -> f64 {
+    INITIAL_DEVIATION
+}
+fn initial_volatility() -> f64 {
+    INITIAL_VOLATILITY
+}
+
+/// the rating systems available
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum System {
+    #[default]
+    Elo,
+    Glicko2,
+}
+
+/// configuration of the ratings
+#[derive(Clone, Copy, Debug, PartialEq, clap::Args)]
+pub struct Config {
+    /// Rating system used to update the ratings
+    #[arg(long = "rating-system", value_enum, default_value_t = System::Elo)]
+    pub system: System,
+    /// Rating of new players
+    #[arg(long, default_value_t = 1500.)]
+    pub initial_rating: f64,
+    #[command(flatten)]
+    pub elo: Elo,
+    #[command(flatten)]
+    pub glicko2: Glicko2,
+}
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            system: System::default(),
+            initial_rating: 1500.,
+            elo: Elo::default(),
+            glicko2: Glicko2::default(),
+        }
+    }
+}
+impl Config {
+    /// a new [`Rating`] of `player`
+    fn new_rating(&self, player: &Player) -> Rating {
+        Rating {
+            name: player.name.clone(),
+            class: player.class,
+            rating: self.initial_rating,
+            deviation: self.glicko2.initial_deviation,
+            volatility: self.glicko2.initial_volatility,
+            games: 0,
+        }
+    }
+    /// `rating` isn't reliable yet
+    pub fn is_provisional(&self, rating: &Rating) -> bool {
+        match self.system {
+            System::Elo => self.elo.is_provisional(rating),
+            System::Glicko2 => self.glicko2.is_provisional(rating),
+        }
+    }
+}
+
+/// [Elo rating system](https://en.wikipedia.org/wiki/Elo_rating_system) configuration
+#[derive(Clone, Copy, Debug, PartialEq, clap::Args)]
+pub struct Elo {
+    /// K-factor: maximum rating change from a single match
+    #[arg(long = "k-factor", default_value_t = 32.)]
+    pub k: f64,
+    /// K-factor of provisional players: they haven't played enough matches yet
+    #[arg(long, default_value_t = 64.)]
+    pub provisional_k: f64,
+    /// Number of rated matches needed for an Elo rating not to be provisional anymore
+    #[arg(long, default_value_t = 10)]
+    pub provisional_games: usize,
+}
+impl Default for Elo {
+    fn default() -> Self {
+        Self {
+            k: 32.,
+            provisional_k: 64.,
+            provisional_games: 10,
+        }
+    }
+}
+impl Elo {
+    /// expected score of a player rated `a` against one rated `b`: probability of winning
+    pub fn expected(a: f64, b: f64) -> f64 {
+        1. / (1. + 10_f64.powf((b - a) / 400.))
+    }
+    /// `rating` hasn't got enough games yet
+    pub fn is_provisional(&self, rating: &Rating) -> bool {
+        rating.games < self.provisional_games
+    }
+    /// K-factor of `rating`
+    fn k(&self, rating: &Rating) -> f64 {
+        if self.is_provisional(rating) {
+            self.provisional_k
+        } else {
+            self.k
+        }
+    }
+}
+
+/// store of [`Rating`]s: a csv file with `name,class,rating,deviation,volatility,games` columns
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ratings(pub Vec<Rating>);
+
+impl Ratings {
+    /// load ratings from file at `path`, empty if it doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        if !path.as_ref().exists() {
+            return Ok(Self::default());
+        }
+        let mut reader = csv::Reader::from_path(path)?;
+        let ratings = reader.deserialize().collect::<Result<_, _>>()?;
+        Ok(Self(ratings))
+    }
+    /// save `self` to file at `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut writer = csv::Writer::from_path(path)?;
+        self.0.iter().try_for_each(|r| writer.serialize(r))?;
+        writer.flush()
+    }
+    /// rating of `player`, if they've got one, see [`Self::position`]
+    pub fn get(&self, player: &Player) -> Option<&Rating> {
+        self.position(player).map(|idx| &self.0[idx])
+    }
+    /// index of the rating of `player`: the one of their name and class,
+    /// or the only one of their name, eg: they've moved up a class since
+    fn position(&self, player: &Player) -> Option<usize> {
+        let exact = |r: &Rating| r.name == player.name && r.class == player.class;
+        self.0.iter().position(exact).or_else(|| {
+            let mut named = (0..self.0.len()).filter(|&idx| self.0[idx].name == player.name);
+            match (named.next(), named.next()) {
+                (Some(idx), None) => Some(idx),
+                _ => None,
+            }
+        })
+    }
+    /// rating of `player`: [`Config::initial_rating`] if they haven't got one
+    pub fn rating_of(&self, player: &Player, config: &Config) -> f64 {
+        self.get(player).map_or(config.initial_rating, |r| r.rating)
+    }
+    /// indices of the ratings of `players`, new ones made for the ones who haven't got one, see [`Self::position`]
+    ///
+    /// every player gets one of their own: players of the same name don't share one,
+    /// the ones of the same class as the rating take it first
+    fn entries(&mut self, players: &[&Player], config: &Config) -> Vec<usize> {
+        let mut idxs = vec![None; players.len()];
+        for exact in [true, false] {
+            for (i, player) in players.iter().enumerate() {
+                if idxs[i].is_some() {
+                    continue;
+                }
+                let found = self.position(player).filter(|&idx| {
+                    (self.0[idx].class == player.class) == exact && !idxs.contains(&Some(idx))
+                });
+                idxs[i] = match found {
+                    Some(idx) => Some(idx),
+                    None if exact => None,
+                    None => {
+                        self.0.push(config.new_rating(player));
+                        Some(self.0.len() - 1)
+                    }
+                };
+            }
+        }
+        idxs.into_iter().flatten().collect()
+    }
+    /// rate the played `duels` of a tournament, byes and unsettled ones are ignored
+    ///
+    /// the tournament is a single rating period: every change is calculated from the ratings before it
+    ///
+    /// returns the rating changes of the players in the order of their first [`Duel`]
+    pub fn update(&mut self, config: &Config, duels: &[Duel]) -> Vec<(Player, f64)> {
+        self.update_with(config, duels, |player| vec![player.clone()])
+    }
+    /// [`Self::update`], but the participants of `duels` are made up of their `members`, eg: pairs of doubles
+    ///
+    /// every member is rated against the average of the members of the other side
+    pub fn update_with(
+        &mut self,
+        config: &Config,
+        duels: &[Duel],
+        members: impl Fn(&Player) -> Vec<Player>,
+    ) -> Vec<(Player, f64)> {
+        // opponents of players: their rating before the tournament and the score against them
+        let mut results: Vec<(Player, Vec<(Rating, f64)>)> = Vec::new();
+        for duel in duels {
+            let Some(outcome) = duel.outcome else {
+                continue;
+            };
+            if duel.homie.is_unset() || duel.guest.is_unset() {
+                continue;
+            }
+            let rating = |player: &Player| {
+                self.get(player)
+                    .cloned()
+                    .unwrap_or_else(|| config.new_rating(player))
+            };
+            // the average of the members
+            let side = |participant: &Player| {
+                let ratings = members(participant).iter().map(rating).collect::<Vec<_>>();
+                let n = ratings.len().max(1) as f64;
+                let mut side = ratings
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| rating(participant));
+                side.rating = ratings.iter().map(|r| r.rating).sum::<f64>() / n;
+                side.deviation = ratings.iter().map(|r| r.deviation).sum::<f64>() / n;
+                side
+            };
+            let score = if outcome { 1. } else { 0. };
+            for (participant, opponent, score) in [
+                (&duel.homie, &duel.guest, score),
+                (&duel.guest, &duel.homie, 1. - score),
+            ] {
+                let opponent = side(opponent);
+                for player in members(participant) {
+                    let result = (opponent.clone(), score);
+                    match results.iter_mut().find(|(p, _)| *p == player) {
+                        Some((_, results)) => results.push(result),
+                        None => results.push((player, vec![result])),
+                    }
+                }
+            }
+        }
+
+        let players = results.iter().map(|(player, _)| player).collect::<Vec<_>>();
+        let idxs = self.entries(&players, config);
+        if config.system == System::Glicko2 {
+            // ratings of the ones not playing get more uncertain
+            for (idx, rating) in self.0.iter_mut().enumerate() {
+                if !idxs.contains(&idx) {
+                    config.glicko2.rate(rating, &[]);
+                }
+            }
+        }
+        let mut changes = Vec::new();
+        for ((player, results), idx) in results.into_iter().zip(idxs) {
+            let rating = &mut self.0[idx];
+            let old = rating.rating;
+            match config.system {
+                System::Elo => {
+                    let k = config.elo.k(rating);
+                    for (opponent, score) in &results {
+                        rating.rating += k * (score - Elo::expected(old, opponent.rating));
+                    }
+                }
+                System::Glicko2 => config.glicko2.rate(rating, &results),
+            }
+            rating.games += results.len();
+            if player.class.is_some() {
+                rating.class = player.class;
+            }
+            changes.push((player, rating.rating - old));
+        }
+        changes
+    }
+    /// the ranking: best first
+    pub fn ranking(&self) -> Vec<&Rating> {
+        let mut ranking = self.0.iter().collect::<Vec<_>>();
+        ranking.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        ranking
+    }
+    /// print [`Self::ranking`], provisional ratings marked with `?`
+    pub fn print(&self, config: &Config) {
+        println!("\n\nRATINGS\n-------\n");
+        for (place, rating) in self.ranking().into_iter().enumerate() {
+            let player = Player {
+                name: rating.name.clone(),
+                class: rating.class,
+                nr: 0,
+            };
+            let mark = if config.is_provisional(rating) {
+                "?"
+            } else {
+                ""
+            };
+            let deviation = if config.system == System::Glicko2 {
+                format!(" ±{:.0}", rating.deviation)
+            } else {
+                String::new()
+            };
+            println!(
+                "{:>3}. {player}: {:.0}{mark}{deviation} ({} games)",
+                place + 1,
+                rating.rating,
+                rating.games
+            );
+        }
+    }
+    /// order `players` by their rating, best first: new players have [`Config::initial_rating`]
+    ///
+    /// stable: players with equal ratings keep their order
+    pub fn sort(&self, players: &mut [Player], config: &Config) {
+        let rating_of = players
+            .iter()
+            .map(|player| (player.clone(), self.rating_of(player, config)))
+            .collect::<HashMap<_, _>>();
+        players.sort_by(|a, b| rating_of[b].total_cmp(&rating_of[a]));
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
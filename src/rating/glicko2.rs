@@ -0,0 +1,131 @@
+use super::{Rating, INITIAL_DEVIATION, INITIAL_VOLATILITY};
+use std::f64::consts::PI;
+
+/// ratio of the Glicko and the Glicko-2 scale
+const SCALE: f64 = 173.7178;
+/// the rating in the middle of the Glicko scale
+const CENTER: f64 = 1500.;
+/// tolerance of the iteration finding the new volatility
+const EPSILON: f64 = 0.000_001;
+
+/// [Glicko-2](http://www.glicko.net/glicko/glicko2.pdf) configuration
+///
+/// every tournament is a rating period
+#[derive(Clone, Copy, Debug, PartialEq, clap::Args)]
+pub struct Glicko2 {
+    /// Glicko-2 system constant: constrains the change of volatility over time, usually 0.3..1.2
+    #[arg(long, default_value_t = 0.5)]
+    pub tau: f64,
+    /// Rating deviation of new players in Glicko-2
+    #[arg(long, default_value_t = INITIAL_DEVIATION)]
+    pub initial_deviation: f64,
+    /// Volatility of new players in Glicko-2
+    #[arg(long, default_value_t = INITIAL_VOLATILITY)]
+    pub initial_volatility: f64,
+    /// Rating deviation above which a Glicko-2 rating is provisional
+    #[arg(long, default_value_t = 110.)]
+    pub provisional_deviation: f64,
+}
+impl Default for Glicko2 {
+    fn default() -> Self {
+        Self {
+            tau: 0.5,
+            initial_deviation: INITIAL_DEVIATION,
+            initial_volatility: INITIAL_VOLATILITY,
+            provisional_deviation: 110.,
+        }
+    }
+}
+
+/// weight of a result against an opponent with a deviation of `phi`
+fn g(phi: f64) -> f64 {
+    1. / (1. + 3. * phi * phi / (PI * PI)).sqrt()
+}
+
+/// expected score of a player of `mu` against one of `mu_j` and `phi_j`
+fn expected(mu: f64, mu_j: f64, phi_j: f64) -> f64 {
+    1. / (1. + (-g(phi_j) * (mu - mu_j)).exp())
+}
+
+impl Glicko2 {
+    /// `rating` is too uncertain yet
+    pub fn is_provisional(&self, rating: &Rating) -> bool {
+        rating.deviation > self.provisional_deviation
+    }
+    /// update `rating` with the `results` of a rating period: opponent's ratings and scores against them
+    ///
+    /// if there are no `results`, only the deviation grows: the rating gets more uncertain
+    pub fn rate(&self, rating: &mut Rating, results: &[(Rating, f64)]) {
+        let mu = (rating.rating - CENTER) / SCALE;
+        let phi = rating.deviation / SCALE;
+        let sigma = rating.volatility;
+
+        if results.is_empty() {
+            let phi = (phi * phi + sigma * sigma).sqrt();
+            rating.deviation = (phi * SCALE).min(self.initial_deviation);
+            return;
+        }
+
+        // (g(phi_j), E, score) of the results
+        let results = results
+            .iter()
+            .map(|(opponent, score)| {
+                let (mu_j, phi_j) = (
+                    (opponent.rating - CENTER) / SCALE,
+                    opponent.deviation / SCALE,
+                );
+                (g(phi_j), expected(mu, mu_j, phi_j), *score)
+            })
+            .collect::<Vec<_>>();
+        // estimated variance of the rating based only on the results
+        let v = 1.
+            / results
+                .iter()
+                .map(|(g, e, _)| g * g * e * (1. - e))
+                .sum::<f64>();
+        let improvement = results.iter().map(|(g, e, s)| g * (s - e)).sum::<f64>();
+        let delta = v * improvement;
+
+        // new volatility: Illinois algorithm
+        let a = (sigma * sigma).ln();
+        let tau = self.tau;
+        let f = |x: f64| {
+            let ex = x.exp();
+            ex * (delta * delta - phi * phi - v - ex) / (2. * (phi * phi + v + ex).powi(2))
+                - (x - a) / (tau * tau)
+        };
+        let mut big_a = a;
+        let mut big_b = if delta * delta > phi * phi + v {
+            (delta * delta - phi * phi - v).ln()
+        } else {
+            let mut k = 1.;
+            while f(a - k * tau) < 0. {
+                k += 1.;
+            }
+            a - k * tau
+        };
+        let (mut f_a, mut f_b) = (f(big_a), f(big_b));
+        while (big_b - big_a).abs() > EPSILON {
+            let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+            let f_c = f(big_c);
+            if f_c * f_b <= 0. {
+                (big_a, f_a) = (big_b, f_b);
+            } else {
+                f_a /= 2.;
+            }
+            (big_b, f_b) = (big_c, f_c);
+        }
+        let sigma = (big_a / 2.).exp();
+
+        let phi_star = (phi * phi + sigma * sigma).sqrt();
+        let phi = 1. / (1. / (phi_star * phi_star) + 1. / v).sqrt();
+        let mu = mu + phi * phi * improvement;
+
+        rating.rating = mu * SCALE + CENTER;
+        rating.deviation = phi * SCALE;
+        rating.volatility = sigma;
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
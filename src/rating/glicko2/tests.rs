@@ -0,0 +1,73 @@
+use super::*;
+use crate::{
+    rating::{Config, Ratings, System},
+    tournament::{Duel, Player},
+};
+
+fn rating(rating: f64, deviation: f64) -> Rating {
+    Rating {
+        name: String::new(),
+        class: None,
+        rating,
+        deviation,
+        volatility: 0.06,
+        games: 0,
+    }
+}
+
+#[test]
+fn rate() {
+    // example of the paper
+    let glicko2 = Glicko2::default();
+    let mut player = rating(1500., 200.);
+    let results = [
+        (rating(1400., 30.), 1.),
+        (rating(1550., 100.), 0.),
+        (rating(1700., 300.), 0.),
+    ];
+    glicko2.rate(&mut player, &results);
+    assert!((player.rating - 1464.06).abs() < 0.01, "{player:?}");
+    assert!((player.deviation - 151.52).abs() < 0.01, "{player:?}");
+    assert!((player.volatility - 0.05999).abs() < 0.00001, "{player:?}");
+
+    // skipping a tournament
+    let before = player.clone();
+    glicko2.rate(&mut player, &[]);
+    assert_eq!(before.rating, player.rating);
+    assert!(player.deviation > before.deviation);
+    assert!(glicko2.is_provisional(&player));
+}
+
+#[test]
+fn absent_players() {
+    let config = Config {
+        system: System::Glicko2,
+        ..Default::default()
+    };
+    let player = |name: &str| Player {
+        name: name.into(),
+        class: None,
+        nr: 0,
+    };
+    let mut ratings = Ratings(vec![
+        Rating {
+            name: "Anna".into(),
+            ..rating(1600., 60.)
+        },
+        Rating {
+            name: "Bob".into(),
+            ..rating(1500., 60.)
+        },
+        Rating {
+            name: "Cecil".into(),
+            ..rating(1500., 60.)
+        },
+    ]);
+    let duels = [Duel::new(player("Anna"), player("Bob")).with_outcome(Some(false))];
+    let changes = ratings.update(&config, &duels);
+    assert_eq!(2, changes.len());
+    assert!(changes[0].1 < 0. && changes[1].1 > 0.);
+    let cecil = ratings.get(&player("Cecil")).unwrap();
+    assert_eq!(1500., cecil.rating);
+    assert!(cecil.deviation > 60.);
+}
@@ -0,0 +1,139 @@
+use super::*;
+
+fn player(name: &str) -> Player {
+    Player {
+        name: name.into(),
+        class: None,
+        nr: 0,
+    }
+}
+
+#[test]
+fn expected() {
+    assert_eq!(0.5, Elo::expected(1500., 1500.));
+    assert!((Elo::expected(1900., 1500.) - 0.909).abs() < 0.001);
+    assert!((Elo::expected(1500., 1900.) + Elo::expected(1900., 1500.) - 1.).abs() < 1e-9);
+}
+
+#[test]
+fn update() {
+    let config = Config {
+        elo: Elo {
+            provisional_games: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (anna, bob, cecil) = (player("Anna"), player("Bob"), player("Cecil"));
+    let mut ratings = Ratings(vec![Rating {
+        games: 5,
+        ..config.new_rating(&anna)
+    }]);
+    let duels = [
+        Duel::new(anna.clone(), bob.clone()).with_outcome(Some(true)),
+        Duel::new(cecil.clone(), anna.clone()).with_outcome(Some(true)),
+        Duel::new(bob.clone(), cecil.clone()).with_outcome(None),
+        Duel::new(bob.clone(), Player::default()).with_outcome(Some(true)),
+    ];
+    let changes = ratings.update(&config, &duels);
+
+    // Anna's established: K = 32, the others are new: K = 64
+    assert_eq!(vec![(anna, 0.), (bob, -32.), (cecil, 32.)], changes);
+    let by_name = |name: &str| ratings.get(&player(name)).unwrap().clone();
+    assert_eq!((1500., 7), (by_name("Anna").rating, by_name("Anna").games));
+    assert_eq!((1468., 1), (by_name("Bob").rating, by_name("Bob").games));
+    assert_eq!(
+        (1532., 1),
+        (by_name("Cecil").rating, by_name("Cecil").games)
+    );
+    assert!(config.is_provisional(&by_name("Cecil")));
+    let ranking = ratings.ranking();
+    let names = ranking.iter().map(|r| r.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(["Cecil", "Anna", "Bob"], names.as_slice());
+
+    let mut players = vec![player("Dora"), player("Bob"), player("Cecil")];
+    ratings.sort(&mut players, &config);
+    assert_eq!(
+        vec![player("Cecil"), player("Dora"), player("Bob")],
+        players
+    );
+}
+
+#[test]
+fn update_with() {
+    let config = Config::default();
+    let mut pairs = crate::tournament::doubles::Pairs::default();
+    let home = pairs.add(vec![player("Anna"), player("Bob")]);
+    let guest = pairs.add(vec![player("Cecil"), player("Dora")]);
+    let duels = [Duel::new(home, guest).with_outcome(Some(true))];
+    let changes = Ratings::default().update_with(&config, &duels, |player| pairs.members(player));
+
+    // the pairs themselves aren't rated, their players equally
+    let names = changes
+        .iter()
+        .map(|(player, _)| player.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(["Anna", "Bob", "Cecil", "Dora"], names.as_slice());
+    assert_eq!(changes[0].1, changes[1].1);
+    assert_eq!(changes[2].1, changes[3].1);
+    assert!(changes[0].1 > 0. && changes[2].1 < 0.);
+}
+
+#[test]
+fn namesakes() {
+    let config = Config::default();
+    let (anna_9, anna_10) = (
+        Player::new("Kovács Anna", Class::new(9, 'A')),
+        Player::new("Kovács Anna", Class::new(10, 'B')),
+    );
+    let bob = player("Bob");
+    let mut ratings = Ratings(vec![Rating {
+        rating: 1700.,
+        ..config.new_rating(&anna_10)
+    }]);
+    // the only one of the name, whatever the class
+    assert_eq!(1700., ratings.rating_of(&anna_9, &config));
+
+    let duels = [
+        Duel::new(anna_9.clone(), bob.clone()).with_outcome(Some(true)),
+        Duel::new(anna_10.clone(), bob.clone()).with_outcome(Some(true)),
+    ];
+    ratings.update(&config, &duels);
+    // the 10B one keeps hers, the 9A one gets a new one
+    assert_eq!(3, ratings.0.len());
+    let of = |player: &Player| ratings.get(player).unwrap().clone();
+    assert_eq!(
+        (Some(Class::new(10, 'B')), 1),
+        (of(&anna_10).class, of(&anna_10).games)
+    );
+    assert_eq!(
+        (Some(Class::new(9, 'A')), 1),
+        (of(&anna_9).class, of(&anna_9).games)
+    );
+    assert!(of(&anna_10).rating > 1700.);
+    assert!(of(&anna_9).rating > config.initial_rating && of(&anna_9).rating < 1700.);
+}
+
+#[test]
+fn store() {
+    let path = std::env::temp_dir().join("tuna-man-ratings.csv");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(Ratings::default(), Ratings::load(&path).unwrap());
+
+    let player = Player::new("Relative Wrasse", Class::new(10, 'C'));
+    let ratings = Ratings(vec![Rating {
+        rating: 1516.5,
+        games: 3,
+        ..Config::default().new_rating(&player)
+    }]);
+    ratings.save(&path).unwrap();
+    assert_eq!(ratings, Ratings::load(&path).unwrap());
+
+    // stores from before Glicko-2
+    std::fs::write(
+        &path,
+        "name,class,rating,games\nRelative Wrasse,10C,1516.5,3\n",
+    )
+    .unwrap();
+    assert_eq!(ratings, Ratings::load(&path).unwrap());
+}
@@ -1,7 +1,36 @@
+use crate::rating::{self, Ratings};
+use class_cup::ClassCup;
+use crosstable::Crosstable;
+pub use error::{Result, TournamentError};
 use format::Format;
-use players::Players;
-use std::path::Path;
+use heat::Heat;
+pub use players::{Diagnostic, Players};
+use simulation::{Outcomes, Simulation};
+#[cfg(not(test))]
+use std::io::Write;
+use std::path::{Path, PathBuf};
+pub use structs::{Branch, Class, Duel, FindError, MatchId, Player};
 
+/// comparing the fairness of formats by simulating them
+pub mod analysis;
+/// drawing brackets of elimination formats
+pub mod bracket;
+/// marking who's present before the tournament starts
+pub mod check_in;
+/// ranking of the classes by the places of their players
+pub mod class_cup;
+/// every player against every other
+pub mod crosstable;
+/// independent tournaments of parts of the players
+pub mod division;
+/// events of pairs of players
+pub mod doubles;
+/// teams drawn from individual players
+pub mod draw;
+/// what can go wrong
+pub mod error;
+/// files the tournament is saved to
+pub mod export;
 /// # the format of the tournament
 ///
 /// ## available formats:
@@ -9,12 +38,30 @@ use std::path::Path;
 /// - [x] [single-knockout](https://en.wikipedia.org/wiki/Single-elimination_tournament)
 /// - [x] [double-knockout](https://en.wikipedia.org/wiki/Double-elimination_tournament)
 /// - [x] [round-robin](https://en.wikipedia.org/wiki/Round-robin_tournament)
-/// - [ ] [swiss-system](https://en.wikipedia.org/wiki/Swiss-system_tournament)
+/// - [x] [swiss-system](https://en.wikipedia.org/wiki/Swiss-system_tournament)
+/// - [x] americano and mexicano: doubles with rotating partners
+/// - [x] heats: matches of any number of players, the best ones of every heat going on
 pub mod format;
+/// matches of any number of players: heats
+pub mod heat;
+/// self-contained html page of the tournament
+pub mod html;
+/// ongoing ladder of challenges, kept between sessions
+pub mod ladder;
+/// estimating the duration of formats
+pub mod planner;
 /// dealing with a bunch of players
 mod players;
+/// commands at the result prompt
+pub mod prompt;
+/// playing tournaments without entering results
+pub mod simulation;
 /// building block structs
 mod structs;
+/// printable svg of the brackets
+pub mod svg;
+/// ties of teams made up of individual rubbers
+pub mod team;
 #[cfg(test)]
 pub mod tests;
 
@@ -22,71 +69,730 @@ pub mod tests;
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Tournament<F: Format> {
     format: F,
+    /// [`Duel`]s of the actual stage
+    duels: Vec<Duel>,
+    /// every [`Duel`] played so far
+    log: Vec<Duel>,
+    /// [`Player`]s who've got no opponent: [`Duel`]s with an unset guest and number 0
+    byes: Vec<Duel>,
+    /// the files `self` is saved to
+    export: export::Export,
+    /// [`Player`]s who've withdrawn: they lose every match still to come
+    withdrawn: Vec<Player>,
+    /// indices of the [`Duel`]s of the actual stage in the order their results are entered, to be undone
+    entered: Vec<usize>,
+    /// points for the places in the class cup, if there's one
+    class_cup: Option<class_cup::Points>,
+    /// the rubbers of a [`Duel`] if it's a tie of teams
+    tie_format: Option<team::TieFormat>,
+    /// members of the teams
+    rosters: team::Rosters,
+    /// every tie entered so far
+    ties: Vec<team::Tie>,
+    /// the individual players of the participants that are pairs or drawn teams
+    pairs: doubles::Pairs,
 }
 
 impl<F: Format> Tournament<F> {
     pub fn new(format: F) -> Self {
-        Self { format }
+        Self {
+            format,
+            duels: Vec::new(),
+            log: Vec::new(),
+            byes: Vec::new(),
+            export: export::Export::default(),
+            withdrawn: Vec::new(),
+            entered: Vec::new(),
+            class_cup: None,
+            tie_format: None,
+            rosters: team::Rosters::default(),
+            ties: Vec::new(),
+            pairs: doubles::Pairs::default(),
+        }
     }
-    /// execute the Tournament with options from `args`
-    pub fn execute(self, args: crate::args::Args) -> std::io::Result<()> {
-        self.players_from_path(&args.file)?.run(args);
-        Ok(())
+    /// run the Tournament of `players` with options from `args`: seeded if so
+    pub fn start(self, mut players: Players, args: crate::args::Args) -> Result<()> {
+        let export = export::Export::from(&args);
+        if !args.seed {
+            return self.with_players(players)?.run(args, export);
+        }
+        let ratings = args.ratings.as_deref().unwrap_or("ratings.csv".as_ref());
+        Ratings::load(ratings)?.sort(&mut players.0, &args.rating);
+        self.with_seeded_players(players)?.run(args, export)
     }
-    /// run the whole Tournament
-    pub fn run(mut self, args: crate::args::Args) {
-        let no_shuffle = args.shuffle.never() || args.shuffle.initially();
-        if args.shuffle.initially() || args.shuffle.always() {
+    /// run the whole Tournament, saving it to the files of `export`
+    pub fn run(mut self, args: crate::args::Args, export: export::Export) -> Result<()> {
+        self.export = export;
+        self.class_cup = args.class_cup.clone();
+        if let Some(format) = &args.tie {
+            self.tie_format = Some(format.clone());
+        }
+        if let Some(path) = &args.rosters {
+            self.rosters = team::Rosters::load(path)?;
+        }
+        if let Some(seed) = args.random_seed {
+            fastrand::seed(seed);
+        }
+        // seeded players stay where they are
+        let no_shuffle = args.seed || args.shuffle.never() || args.shuffle.initially();
+        if !args.seed && (args.shuffle.initially() || args.shuffle.always()) {
             self.format.initial_shuffle();
         }
+        let brackets = !self.format.branches().is_empty();
+        if let Some(path) = self.export.svg_empty.as_ref().filter(|_| brackets) {
+            std::fs::write(path, self.svg(false))?;
+        }
 
         // number of rounds
         let mut round = 0;
 
         // run till we've got all the results
+        let outcomes = match (args.simulate, &args.file) {
+            (None, _) => None,
+            (Some(Simulation::Rating), Some(file)) => Some(Outcomes::rated_from_path(file)?),
+            (Some(_), _) => Some(Outcomes::Uniform),
+        };
+
+        // simulated rounds are played out without printing them
+        self.set_quiet(outcomes.is_some());
         while !self.is_end() {
-            // winner branch duels this round
-            println!("\n\n\n\nRound {round}.\n");
-            self.format.print_status();
-            self.play_next_round(no_shuffle);
+            if let Some(outcomes) = &outcomes {
+                self.play_next_round_by(no_shuffle, |duel, tment| {
+                    outcomes.decide_by(duel, |player| tment.members(player))
+                })?;
+            } else {
+                // winner branch duels this round
+                println!("\n\n\n\nRound {round}.\n");
+                self.print_status();
+                self.play_next_round(no_shuffle)?;
+            }
+            self.save_files()?;
 
             round += 1;
         }
+        if outcomes.is_some() {
+            self.export();
+        }
 
-        let mut knocked = self.format.results();
+        self.print_status();
+        let mut knocked = self.format.clone().results();
         // printing results
         println!("\nTournament ended in {round} rounds, Results:");
         println!("\n\nPODIUM\n------\n");
-        println!("Winner: {}", knocked.0.pop().unwrap());
-        println!("Second place: {}", knocked.0.pop().unwrap());
-        println!("Third place: {}", knocked.0.pop().unwrap());
+        for title in ["Winner", "Second place", "Third place"] {
+            if let Some(player) = knocked.0.pop() {
+                println!("{title}: {player}");
+            }
+        }
         println!("\nrunner-ups\n");
         for (place, player) in knocked.0.iter().rev().enumerate() {
             println!("{}. place: {player}", place + 4);
         }
+        if let Some(cup) = self.class_cup() {
+            println!("\n\nCLASS CUP\n---------\n\n{}", cup.render());
+        }
+        println!("\n\nMATCHES\n-------\n");
+        for duel in &self.log {
+            match self.tie(duel.id) {
+                Some(tie) => println!("{}. {duel} ({tie})", duel.id),
+                None => println!("{}. {duel}", duel.id),
+            }
+        }
+        if let Some(path) = &args.ratings {
+            self.update_ratings(path, &args.rating)?;
+        }
+        Ok(())
+    }
+    /// update the [`Ratings`] kept at `path` with the played [`Duel`]s and print the changes
+    pub fn update_ratings(
+        &self,
+        path: impl AsRef<Path>,
+        config: &rating::Config,
+    ) -> std::io::Result<()> {
+        let mut ratings = Ratings::load(&path)?;
+        // pairs and teams are rated by their players
+        let changes = ratings.update_with(config, &self.log, |player| self.members(player));
+        ratings.save(path)?;
+
+        println!("\n\nRATING CHANGES\n--------------\n");
+        for (player, change) in changes {
+            let rating = ratings.rating_of(&player, config);
+            println!("{player}: {rating:.0} ({change:+.0})");
+        }
+        Ok(())
+    }
+    /// the individual players of `participant`: the members of pairs and teams, or of partners made up by the format,
+    /// see [`Format::members`]
+    pub fn members(&self, participant: &Player) -> Vec<Player> {
+        match self.pairs.0.contains_key(participant) {
+            true => self.pairs.members(participant),
+            false => self.format.members(participant),
+        }
     }
     /// `self` but with `players`
-    pub fn with_players(mut self, players: Players) -> Self {
-        assert!(
-            players.0.len() >= 3,
-            "you need at least 3 participants to play a tournament"
-        );
+    ///
+    /// # Errors
+    ///
+    /// if there aren't enough `players` to play a [`Tournament`], or the format can't be played with them,
+    /// see [`Format::check_count`]
+    pub fn with_players(mut self, mut players: Players) -> Result<Self> {
+        check_enough(&players)?;
+        self.format.check_count(players.0.len())?;
+        players.number_duplicates();
         self.format.add_players(players);
 
-        self
+        Ok(self)
+    }
+    /// `self` but with `players` ordered by their strength, best first, see [`Format::add_seeded_players`]
+    ///
+    /// # Errors
+    ///
+    /// if there aren't enough `players` to play a [`Tournament`], or the format can't be played with them,
+    /// see [`Format::check_count`]
+    pub fn with_seeded_players(mut self, mut players: Players) -> Result<Self> {
+        check_enough(&players)?;
+        self.format.check_count(players.0.len())?;
+        players.number_duplicates();
+        self.format.add_seeded_players(players);
+
+        Ok(self)
     }
     /// add players to `self` read from file at `path`
-    pub fn players_from_path(self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+    pub fn players_from_path(self, path: impl AsRef<Path>) -> Result<Self> {
         let players = Players::load(path)?;
-        Ok(self.with_players(players))
+        self.with_players(players)
+    }
+    /// the format doesn't print what's happening if `quiet`, see [`Format::set_quiet`]
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.format.set_quiet(quiet);
     }
     /// `self` is ended, we've got all the results
     pub fn is_end(&self) -> bool {
         self.format.is_end()
     }
-    /// play the next round
-    pub fn play_next_round(&mut self, standard: bool) {
-        self.format.play_round(standard);
+    /// play the next round: all of it's stages
+    ///
+    /// if `standard`, then the original order is preserved, otherwise players are shuffled before every stage
+    pub fn play_next_round(&mut self, standard: bool) -> Result<()> {
+        while let Some(duels) = self.format.next_duels(!standard) {
+            self.start_stage(duels);
+            self.print_duels();
+            self.export();
+            // the stage stays open till it's confirmed, so that its last result can be taken back
+            while !self.read_result()? {}
+            self.finish_stage()?;
+            self.export();
+        }
+        Ok(())
+    }
+    /// `duels` make up the actual stage, with their ids given by the format
+    fn start_stage(&mut self, duels: Vec<Duel>) {
+        if let Some(id) = duels.first().map(|duel| duel.id) {
+            let byes = self.format.byes().into_iter().map(|player| Duel {
+                id: MatchId { nr: 0, ..id },
+                ..Duel::new(player, Player::default()).with_outcome(Some(true))
+            });
+            self.byes.extend(byes);
+        }
+        self.duels = duels;
+        self.entered.clear();
+        self.walkovers();
+    }
+    /// apply the settled [`Duel`]s of the actual stage
+    ///
+    /// # Errors
+    ///
+    /// if any of them hasn't got an outcome, nothing's changed then
+    fn finish_stage(&mut self) -> Result<()> {
+        self.format.apply(self.duels.clone())?;
+        let duels = std::mem::take(&mut self.duels);
+        self.log.extend(duels);
+        Ok(())
+    }
+    /// every [`Duel`] played so far
+    pub fn log(&self) -> &[Duel] {
+        &self.log
+    }
+    /// every [`Duel`] so far: played, pending ones and byes
+    pub fn matches(&self) -> Vec<Duel> {
+        [&self.log, &self.duels, &self.byes]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+    /// print the actual status
+    pub fn print_status(&self) {
+        self.format.print_status(&self.matches());
+    }
+    /// `self` but with the html page at `path` regenerated after every result
+    pub fn with_html(mut self, path: impl Into<PathBuf>) -> Self {
+        self.export.html = Some(path.into());
+        self
+    }
+    /// the actual status as a self-contained html page, see [`html::render`]
+    pub fn html(&self) -> String {
+        html::render(&self.format, &self.matches(), self.class_cup().as_ref())
+    }
+    /// `self` but with a class cup: the classes get `points` for the places of their players
+    pub fn with_class_cup(self, points: class_cup::Points) -> Self {
+        Self {
+            class_cup: Some(points),
+            ..self
+        }
+    }
+    /// the actual class cup, if there's one, see [`ClassCup::new`]
+    pub fn class_cup(&self) -> Option<ClassCup> {
+        self.class_cup
+            .as_ref()
+            .map(|points| ClassCup::new(&self.standings(), points, |player| self.members(player)))
+    }
+    /// `self` but with the svg of the brackets at `path` regenerated after every result,
+    /// made for `paper`, in `landscape` orientation if so
+    pub fn with_svg(
+        mut self,
+        path: impl Into<PathBuf>,
+        paper: svg::Paper,
+        landscape: bool,
+    ) -> Self {
+        self.export = export::Export {
+            svg: Some(path.into()),
+            paper,
+            landscape,
+            ..self.export
+        };
+        self
+    }
+    /// the brackets as a printable svg, see [`svg::render`]
+    ///
+    /// if `filled`, results and players known so far are written into it,
+    /// otherwise it's empty: only players of the first round are there
+    pub fn svg(&self, filled: bool) -> String {
+        let plan = self.plan();
+        let matches = self.matches();
+        let known = filled.then_some(matches.as_slice());
+        svg::render(
+            self.format.branches(),
+            &plan,
+            known,
+            self.export.paper,
+            self.export.landscape,
+        )
+    }
+    /// every [`Duel`] of the whole `self` in the order they're played, byes included:
+    /// the ones to come are played out with homies winning
+    fn plan(&self) -> Vec<Duel> {
+        let mut planned = self.clone();
+        // every outcome is decided: nothing can go wrong
+        planned.set_quiet(true);
+        let _ = planned.play_out(true, |_| true);
+        let mut plan = planned.log;
+        // byes are put in front of the stage they belong to
+        for bye in planned.byes {
+            let idx = plan
+                .iter()
+                .position(|duel| duel.id.branch == bye.id.branch && duel.id.round == bye.id.round)
+                .unwrap_or(plan.len());
+            plan.insert(idx, bye);
+        }
+        plan
+    }
+    /// play `self` till it's end, outcomes of the [`Duel`]s are `decide`d: `true` if homie wins
+    ///
+    /// `standard`: see [`Self::play_next_round`]
+    pub fn play_out(
+        &mut self,
+        standard: bool,
+        mut decide: impl FnMut(&Duel) -> bool,
+    ) -> Result<()> {
+        // the actual stage first
+        for duel in &mut self.duels {
+            if duel.outcome.is_none() {
+                duel.outcome = Some(decide(duel));
+            }
+        }
+        if !self.duels.is_empty() {
+            self.finish_stage()?;
+        }
+        // the rest of the actual round, then the others
+        self.play_next_round_with(standard, &mut decide)?;
+        while !self.is_end() {
+            self.play_next_round_with(standard, &mut decide)?;
+        }
+        Ok(())
+    }
+    /// play the next round: all of it's stages, outcomes of the [`Duel`]s are `decide`d: `true` if homie wins
+    ///
+    /// `standard`: see [`Self::play_next_round`]
+    pub fn play_next_round_with(
+        &mut self,
+        standard: bool,
+        mut decide: impl FnMut(&Duel) -> bool,
+    ) -> Result<()> {
+        self.play_next_round_by(standard, |duel, _| decide(duel))
+    }
+    /// [`Self::play_next_round_with`], but `decide` is given `self` as well, eg: for the members of the participants
+    fn play_next_round_by(
+        &mut self,
+        standard: bool,
+        mut decide: impl FnMut(&Duel, &Self) -> bool,
+    ) -> Result<()> {
+        while self.play_next_stage_by(standard, &mut decide)?.is_some() {}
+        Ok(())
+    }
+    /// play the next stage of the actual round, outcomes of the [`Duel`]s are `decide`d: `true` if homie wins
+    ///
+    /// `standard`: see [`Self::play_next_round`]
+    ///
+    /// returns the number of [`Duel`]s played, `None` if there are no more stages in the round
+    pub fn play_next_stage_with(
+        &mut self,
+        standard: bool,
+        mut decide: impl FnMut(&Duel) -> bool,
+    ) -> Result<Option<usize>> {
+        self.play_next_stage_by(standard, |duel, _| decide(duel))
+    }
+    /// [`Self::play_next_stage_with`], but `decide` is given `self` as well
+    fn play_next_stage_by(
+        &mut self,
+        standard: bool,
+        mut decide: impl FnMut(&Duel, &Self) -> bool,
+    ) -> Result<Option<usize>> {
+        let Some(duels) = self.format.next_duels(!standard) else {
+            return Ok(None);
+        };
+        self.start_stage(duels);
+        let mut duels = std::mem::take(&mut self.duels);
+        // walkovers are already decided
+        for duel in duels.iter_mut().filter(|duel| duel.outcome.is_none()) {
+            duel.outcome = Some(decide(duel, self));
+        }
+        let played = duels.len();
+        self.duels = duels;
+        self.finish_stage()?;
+        Ok(Some(played))
+    }
+    /// play `self` till it's end without printing anything, winners are chosen by `outcomes`, see [`Self::set_quiet`]
+    ///
+    /// `standard`: see [`Self::play_next_round`]
+    ///
+    /// returns the number of rounds played
+    pub fn simulate(&mut self, standard: bool, outcomes: &Outcomes) -> Result<usize> {
+        self.set_quiet(true);
+        let mut rounds = 0;
+        while !self.is_end() {
+            self.play_next_round_by(standard, |duel, tment| {
+                outcomes.decide_by(duel, |player| tment.members(player))
+            })?;
+            rounds += 1;
+        }
+        Ok(rounds)
+    }
+    /// the actual standings, best first
+    pub fn standings(&self) -> Vec<format::Standing> {
+        self.format.standings()
+    }
+    /// regenerate the html page and the svg if needed
+    fn export(&self) {
+        if let Some(path) = &self.export.html {
+            if let Err(e) = std::fs::write(path, self.html()) {
+                eprintln!("error: couldn't write html page to {}: {e}", path.display());
+            }
+        }
+        if let Some(path) = self
+            .export
+            .svg
+            .as_ref()
+            .filter(|_| !self.format.branches().is_empty())
+        {
+            if let Err(e) = std::fs::write(path, self.svg(true)) {
+                eprintln!("error: couldn't write svg to {}: {e}", path.display());
+            }
+        }
+    }
+    /// save the log, the crosstable and the class cup to their files, if they're given,
+    /// see [`Self::save_log`], [`Self::save_crosstable`], [`Self::save_class_cup`]
+    fn save_files(&self) -> Result<()> {
+        if let Some(path) = &self.export.log {
+            self.save_log(path)?;
+        }
+        if let Some(path) = &self.export.crosstable {
+            self.save_crosstable(path)?;
+        }
+        if let Some(path) = &self.export.class_cup {
+            self.save_class_cup(path)?;
+        }
+        Ok(())
+    }
+    /// save everything to their files now: the log, the crosstable, the html page and the svg, if they're given
+    pub fn save(&self) -> Result<()> {
+        self.save_files()?;
+        self.export();
+        Ok(())
+    }
+    /// save [`Self::log`] to a csv file at `path`
+    pub fn save_log(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut writer = csv::Writer::from_path(path)?;
+        // ties have got a score as well
+        let ties = self.tie_format.is_some();
+        let mut header = vec!["match", "homie", "guest", "winner"];
+        if ties {
+            header.push("score");
+        }
+        writer.write_record(header)?;
+        for duel in &self.log {
+            let winner = match duel.outcome {
+                Some(true) => duel.homie.to_string(),
+                Some(false) => duel.guest.to_string(),
+                None => String::new(),
+            };
+            let mut record = vec![
+                duel.id.to_string(),
+                duel.homie.to_string(),
+                duel.guest.to_string(),
+                winner,
+            ];
+            if ties {
+                record.push(
+                    self.tie(duel.id)
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                );
+            }
+            writer.write_record(record)?;
+        }
+        writer.flush()
+    }
+    /// every player against every other, if there are no brackets in the format
+    pub fn crosstable(&self) -> Option<Crosstable> {
+        self.format
+            .branches()
+            .is_empty()
+            .then(|| Crosstable::from_standings(&self.format.standings(), &self.matches()))
+    }
+    /// save [`Self::crosstable`] to a file at `path`: markdown table if it's extension is `md`, csv otherwise
+    ///
+    /// nothing's saved if there's no crosstable
+    pub fn save_crosstable(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let Some(table) = self.crosstable() else {
+            return Ok(());
+        };
+        let path = path.as_ref();
+        if path.extension().is_some_and(|ext| ext == "md") {
+            std::fs::write(path, table.markdown())
+        } else {
+            Ok(table.write_csv(std::fs::File::create(path)?)?)
+        }
+    }
+    /// save [`Self::class_cup`] to a file at `path`: markdown table if it's extension is `md`, csv otherwise
+    ///
+    /// nothing's saved if there's no class cup
+    pub fn save_class_cup(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        match self.class_cup() {
+            Some(cup) => cup.save(path),
+            None => Ok(()),
+        }
+    }
+    /// the [`Duel`]s of the actual stage
+    pub fn duels(&self) -> &[Duel] {
+        &self.duels
+    }
+    /// every [`Duel`] of the actual stage has an outcome
+    pub fn is_settled(&self) -> bool {
+        self.duels.iter().all(|duel| duel.outcome.is_some())
+    }
+    /// a new [`Player`] enters the running `self`, see [`Format::enter_late`]
+    ///
+    /// `input`: `<name>[, <class>]`
+    ///
+    /// # Errors
+    ///
+    /// if `input` is invalid, there's already such a player, or the format doesn't let them enter
+    pub fn enter_late(&mut self, input: &str) -> Result<Player> {
+        let player = Player::try_from(input).map_err(TournamentError::InvalidInput)?;
+        let taken = self.standings().iter().any(|standing| {
+            standing.player.name == player.name && standing.player.class == player.class
+        });
+        if taken {
+            return Err(TournamentError::LateEntry(
+                "there's already a player with this name and class",
+            ));
+        }
+        // the walkovers of the rounds already started
+        let walkovers = self.format.enter_late(player.clone())?;
+        self.log.extend(walkovers);
+        Ok(player)
+    }
+    /// set the outcome of a [`Duel`] of the actual stage, as `input` tells it
+    ///
+    /// `input`: see [`Duel::find_outcome`]
+    pub fn enter_result(&mut self, input: &str) -> Result<&Duel> {
+        let (idx, outcome) = Duel::find_outcome(&self.duels, input)
+            .map_err(|e| TournamentError::InvalidResult(e.as_str()))?;
+        self.entered.push(idx);
+        let duel = &mut self.duels[idx];
+        duel.outcome = Some(outcome);
+        Ok(duel)
+    }
+    /// rank the players of a heat of the actual stage as `input` tells it: `<id> <ranking>`,
+    /// where `<id>` is either a [`MatchId`] or just the number of the heat, for `<ranking>` see [`Heat::rank`]
+    ///
+    /// every [`Duel`] of the heat is settled by the ranking, but the walkovers of withdrawn players
+    pub fn enter_ranking(&mut self, input: &str) -> Result<Heat> {
+        let (id, ranking) = input
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or(TournamentError::InvalidResult("expected <heat> <ranking>"))?;
+        let fits = |heat: &Heat| match id.parse::<MatchId>() {
+            Ok(id) => heat.id == id,
+            Err(_) => id.parse() == Ok(heat.id.nr),
+        };
+        let mut heat = self
+            .format
+            .heats()
+            .into_iter()
+            .find(fits)
+            .ok_or(TournamentError::InvalidResult("no such heat in this stage"))?;
+        heat.rank(ranking).map_err(TournamentError::InvalidResult)?;
+        for ranked in heat.duels() {
+            let walkover =
+                self.withdrawn.contains(&ranked.homie) || self.withdrawn.contains(&ranked.guest);
+            let idx = self.duels.iter().position(|duel| {
+                (duel.id, &duel.homie, &duel.guest) == (ranked.id, &ranked.homie, &ranked.guest)
+            });
+            if let Some(idx) = idx.filter(|_| !walkover) {
+                self.duels[idx].outcome = ranked.outcome;
+                self.entered.push(idx);
+            }
+        }
+        Ok(heat)
+    }
+    /// `self` but with ties of teams made up of the rubbers of `format`, lineups from `rosters`
+    pub fn with_ties(self, format: team::TieFormat, rosters: team::Rosters) -> Self {
+        Self {
+            tie_format: Some(format),
+            rosters,
+            ..self
+        }
+    }
+    /// `self` but with participants made up of `pairs`, or teams of players: they're rated individually
+    pub fn with_pairs(self, pairs: doubles::Pairs) -> Self {
+        Self { pairs, ..self }
+    }
+    /// the tie entered for the [`Duel`] with `id`, if there's one
+    pub fn tie(&self, id: MatchId) -> Option<&team::Tie> {
+        self.ties.iter().find(|tie| tie.id == id)
+    }
+    /// enter the tie of the [`Duel`] of the actual stage with `id`:
+    /// the lineups of the `home` and `away` teams and the `results` of the rubbers,
+    /// the winner of the tie wins the [`Duel`]
+    ///
+    /// for `home` and `away` see [`team::Rosters::lineup`], for `results` see [`team::Tie::with_results`]
+    ///
+    /// # Errors
+    ///
+    /// if there are no ties in `self`, or any of the inputs is invalid
+    pub fn enter_tie(
+        &mut self,
+        id: &str,
+        home: &str,
+        away: &str,
+        results: &str,
+    ) -> Result<&team::Tie> {
+        let format = self
+            .tie_format
+            .as_ref()
+            .ok_or(TournamentError::InvalidInput("no ties in this tournament"))?;
+        let idx =
+            Duel::find(&self.duels, id).map_err(|e| TournamentError::InvalidInput(e.as_str()))?;
+        let duel = &self.duels[idx];
+        let (home_positions, away_positions) = format.positions();
+        let doubles = format.has_doubles();
+        let lineup = |team: &Player, input: &str, positions: usize| {
+            self.rosters
+                .lineup(team, input, positions, doubles)
+                .map_err(TournamentError::InvalidInput)
+        };
+        let tie = team::Tie {
+            id: duel.id,
+            home: lineup(&duel.homie, home, home_positions)?,
+            away: lineup(&duel.guest, away, away_positions)?,
+            results: Vec::new(),
+        }
+        .with_results(format, results)
+        .map_err(TournamentError::InvalidResult)?;
+
+        self.duels[idx].outcome = tie.winner(format);
+        self.entered.push(idx);
+        self.ties.retain(|other| other.id != tie.id);
+        self.ties.push(tie);
+        Ok(&self.ties[self.ties.len() - 1])
+    }
+    /// print the [`Duel`]s of the actual stage
+    fn print_duels(&self) {
+        let heats = self.format.heats();
+        for heat in &heats {
+            println!("\n    {}", heat.id);
+            for (i, player) in heat.players.iter().enumerate() {
+                println!("    {:>3}. {player}", i + 1);
+            }
+        }
+        if !heats.is_empty() {
+            return;
+        }
+        for duel in &self.duels {
+            println!("    {}. {duel}", duel.id);
+        }
+    }
+    #[cfg(test)]
+    /// homie wins the first unsettled duel, the stage is confirmed once it's settled
+    fn read_result(&mut self) -> Result<bool> {
+        if let Some(duel) = self.duels.iter_mut().find(|duel| duel.outcome.is_none()) {
+            duel.outcome = Some(true);
+        }
+        Ok(self.is_settled())
+    }
+    #[cfg(not(test))]
+    /// read a result or a command from stdin, and execute it, see [`Self::prompt`]
+    ///
+    /// returns `true` if the actual stage is settled and confirmed
+    ///
+    /// # Errors
+    ///
+    /// [`TournamentError::Quit`] if the user's quit or there's nothing more to read, after saving everything
+    fn read_result(&mut self) -> Result<bool> {
+        if self.is_settled() {
+            print!("every result is in ('next' or <enter> to go on, 'undo' to take back): ");
+        } else if self.format.heats().is_empty() {
+            print!("result (<id> <winner> or <winner>, 'help' for commands): ");
+        } else {
+            print!("ranking (<heat> <numbers or names in order>, 'help' for commands): ");
+        }
+        let mut buf = String::new();
+        if std::io::stdout().flush().is_err() || std::io::stdin().read_line(&mut buf).is_err() {
+            println!("invalid input");
+            return Ok(false);
+        }
+        // end of input: nothing more to read
+        let flow = if buf.is_empty() {
+            prompt::Flow::Quit
+        } else {
+            self.prompt(&buf).unwrap_or_else(|e| {
+                println!("{e}");
+                prompt::Flow::Stay
+            })
+        };
+        match flow {
+            prompt::Flow::Stay => {
+                self.export();
+                Ok(false)
+            }
+            prompt::Flow::Next => Ok(true),
+            prompt::Flow::Quit => {
+                self.save()?;
+                Err(TournamentError::Quit)
+            }
+        }
     }
     // pub fn execute(
     //     &mut self,
@@ -113,3 +819,78 @@ impl<F: Format> Tournament<F> {
     //     Ok(())
     // }
 }
+
+/// execute the [`Tournament`] of the participants in the file of `args` in `args.format`, or in heats if so,
+/// with options from `args`
+pub fn execute(args: crate::args::Args) -> Result<()> {
+    let Some(file) = &args.file else {
+        return Err(std::io::Error::other("no file with participants given").into());
+    };
+    let rows = Players::load_rows(file, args.strict)?;
+    let (players, pairs) = participants(rows, &args)?;
+    let heats = args
+        .heats
+        .map(|size| format::Heats::new(size.into(), args.advance.into()))
+        .transpose()?;
+    let (supported, start) = (
+        args.format,
+        Start {
+            players,
+            pairs,
+            args,
+        },
+    );
+    match heats {
+        Some(heats) => format::Run::run(start, heats),
+        None => supported.run(start),
+    }
+}
+
+/// the participants of a [`Tournament`] of the players of `rows` with options from `args`:
+/// pairs of doubles or drawn teams, the ones present at the check-in,
+/// and the individual players of them
+pub(crate) fn participants(
+    rows: Vec<players::Row>,
+    args: &crate::args::Args,
+) -> Result<(Players, doubles::Pairs)> {
+    let (ratings, config) = (args.ratings.as_deref(), &args.rating);
+    let (mut players, mut pairs) = match args.doubles {
+        Some(pairing) => doubles::pair_up(rows, pairing, ratings, config)?,
+        None => (
+            Players(rows.into_iter().map(|row| row.player).collect()),
+            doubles::Pairs::default(),
+        ),
+    };
+    if let Some(size) = args.draw_teams {
+        (players, pairs) = draw::draw_teams(players, size.into(), args.balance, ratings, config)?;
+    }
+    if args.check_in && args.simulate.is_none() {
+        players = check_in::run(players)?;
+    }
+    Ok((players, pairs))
+}
+
+/// start the [`Tournament`] of `players` made up of `pairs` with options from `args`, see [`Tournament::start`]
+pub struct Start {
+    pub players: Players,
+    pub pairs: doubles::Pairs,
+    pub args: crate::args::Args,
+}
+
+impl format::Run for Start {
+    type Output = Result<()>;
+
+    fn run<F: Format>(self, format: F) -> Result<()> {
+        Tournament::new(format)
+            .with_pairs(self.pairs)
+            .start(self.players, self.args)
+    }
+}
+
+/// error if there aren't enough `players` to play a [`Tournament`]
+fn check_enough(players: &Players) -> Result<()> {
+    match players.0.len() {
+        n if n < 3 => Err(TournamentError::NotEnoughPlayers(n)),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,206 @@
+use super::{
+    format::{self, Format, Supported},
+    simulation::Outcomes,
+    Players, Result, Tournament, TournamentError,
+};
+use crate::rating::Ratings;
+use std::path::Path;
+
+/// how fair a [`Supported`] format is for a field of [`Players`], averaged over simulated runs
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub format: Supported,
+    /// number of simulated runs
+    pub runs: usize,
+    /// share of the runs the strongest player has won, shared first places count proportionally
+    pub strongest_wins: f64,
+    /// Spearman's rank correlation between the ratings and the final standings: 1 is perfect
+    pub rank_correlation: f64,
+    /// average number of matches played
+    pub matches: f64,
+    /// average number of rounds played
+    pub rounds: f64,
+}
+
+/// simulate every [`Supported`] format that can be played with `players` `runs` times, winners chosen by `outcomes`
+///
+/// the players are shuffled before every run, as by default
+pub fn analyze(players: &Players, outcomes: &Outcomes, runs: usize) -> Result<Vec<Report>> {
+    use clap::ValueEnum;
+    Supported::value_variants()
+        .iter()
+        .map(|format| {
+            format.run(Simulate {
+                supported: *format,
+                players,
+                outcomes,
+                runs,
+            })
+        })
+        // the ones that can't be played with this many players
+        .filter(|report| !matches!(report, Err(TournamentError::InvalidCount(_))))
+        .collect()
+}
+
+/// simulating a [`Supported`] format, see [`simulate`]
+struct Simulate<'a> {
+    supported: Supported,
+    players: &'a Players,
+    outcomes: &'a Outcomes,
+    runs: usize,
+}
+
+impl format::Run for Simulate<'_> {
+    type Output = Result<Report>;
+
+    fn run<F: Format>(self, format: F) -> Result<Report> {
+        simulate(
+            format,
+            self.supported,
+            self.players,
+            self.outcomes,
+            self.runs,
+        )
+    }
+}
+
+/// [`Report`] of `format` from `runs` simulated runs
+fn simulate<F: Format>(
+    format: F,
+    supported: Supported,
+    players: &Players,
+    outcomes: &Outcomes,
+    runs: usize,
+) -> Result<Report> {
+    // ratings of the players, in their order
+    let ratings = players
+        .0
+        .iter()
+        .map(|player| outcomes.rating(player))
+        .collect::<Vec<_>>();
+    let strongest = ratings
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| &players.0[i]);
+    let expected_ranks = ranks(&ratings.iter().map(|r| -r).collect::<Vec<_>>());
+
+    let mut report = Report {
+        format: supported,
+        runs,
+        strongest_wins: 0.,
+        rank_correlation: 0.,
+        matches: 0.,
+        rounds: 0.,
+    };
+    for _ in 0..runs {
+        let mut tment = Tournament::new(format.clone()).with_players(players.clone())?;
+        tment.format.initial_shuffle();
+        report.rounds += tment.simulate(true, outcomes)? as f64;
+        report.matches += tment.log().len() as f64;
+
+        let standings = tment.standings();
+        let winners = standings.iter().filter(|s| s.place == 1).count();
+        if standings
+            .iter()
+            .any(|s| s.place == 1 && Some(&s.player) == strongest)
+        {
+            report.strongest_wins += 1. / winners as f64;
+        }
+        let places = players
+            .0
+            .iter()
+            .map(|player| {
+                standings
+                    .iter()
+                    .find(|s| &s.player == player)
+                    .map_or(standings.len(), |s| s.place) as f64
+            })
+            .collect::<Vec<_>>();
+        report.rank_correlation += correlation(&expected_ranks, &ranks(&places));
+    }
+    let runs = runs.max(1) as f64;
+    report.strongest_wins /= runs;
+    report.rank_correlation /= runs;
+    report.matches /= runs;
+    report.rounds /= runs;
+    Ok(report)
+}
+
+/// ranks of `values`, the smallest being 1, equal ones get the average of their ranks
+fn ranks(values: &[f64]) -> Vec<f64> {
+    values
+        .iter()
+        .map(|value| {
+            let less = values.iter().filter(|v| *v < value).count();
+            let equal = values.iter().filter(|v| *v == value).count();
+            less as f64 + (equal as f64 + 1.) / 2.
+        })
+        .collect()
+}
+
+/// Pearson correlation of `xs` and `ys`, 0 if either of them is constant
+fn correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len() as f64;
+    let (mean_x, mean_y) = (xs.iter().sum::<f64>() / n, ys.iter().sum::<f64>() / n);
+    let cov = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let var = |values: &[f64], mean: f64| values.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+    let (var_x, var_y) = (var(xs, mean_x), var(ys, mean_y));
+    if var_x == 0. || var_y == 0. {
+        return 0.;
+    }
+    cov / (var_x * var_y).sqrt()
+}
+
+/// analyze the participants in `file`, their strength is from `ratings` if given, from its 'rating' column otherwise
+pub fn execute(
+    file: &Path,
+    ratings: Option<&Path>,
+    runs: usize,
+    random_seed: Option<u64>,
+) -> Result<()> {
+    if let Some(seed) = random_seed {
+        fastrand::seed(seed);
+    }
+    let players = Players::load(file)?;
+    let outcomes = match ratings {
+        Some(path) => Outcomes::Rated(
+            Ratings::load(path)?
+                .0
+                .into_iter()
+                .map(|rating| (rating.name, rating.rating))
+                .collect(),
+        ),
+        None => Outcomes::rated_from_path(file)?,
+    };
+    print(&analyze(&players, &outcomes, runs)?);
+    Ok(())
+}
+
+/// print `reports` as a table
+pub fn print(reports: &[Report]) {
+    println!(
+        "\n{:<20} {:>15} {:>17} {:>8} {:>7}",
+        "FORMAT", "STRONGEST WINS", "RANK CORRELATION", "MATCHES", "ROUNDS"
+    );
+    for report in reports {
+        println!(
+            "{:<20} {:>14.1}% {:>17.2} {:>8.1} {:>7.1}",
+            report.format.name(),
+            report.strongest_wins * 100.,
+            report.rank_correlation,
+            report.matches,
+            report.rounds
+        );
+    }
+    if let Some(runs) = reports.first().map(|r| r.runs) {
+        println!("\naverages of {runs} simulated runs of every format");
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
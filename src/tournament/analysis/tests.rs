@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn rank_correlation() {
+    assert_eq!(vec![1., 2.5, 2.5, 4.], ranks(&[1., 5., 5., 7.]));
+    assert_eq!(1., correlation(&[1., 2., 3.], &[2., 4., 6.]));
+    assert_eq!(-1., correlation(&[1., 2., 3.], &[3., 2., 1.]));
+    assert_eq!(0., correlation(&[1., 2., 3.], &[1., 1., 1.]));
+}
+
+#[test]
+fn analyze() {
+    let players = Players::load("data.csv").unwrap();
+    fastrand::seed(7);
+    // the first one is by far the strongest, the rest are in order as well
+    let ratings = players
+        .0
+        .iter()
+        .enumerate()
+        .map(|(i, player)| (player.name.clone(), 2500. - i as f64 * 300.))
+        .collect();
+    let reports = super::analyze(&players, &Outcomes::Rated(ratings), 50).unwrap();
+    assert_eq!(6, reports.len());
+    for report in &reports {
+        // in doubles the strongest one depends on their partners, still better than by chance
+        let strongest_wins = match report.format {
+            Supported::Americano | Supported::Mexicano => 0.2,
+            _ => 0.5,
+        };
+        assert!(report.strongest_wins > strongest_wins, "{report:?}");
+        assert!(report.rank_correlation > 0.5, "{report:?}");
+    }
+    let round_robin = &reports[2];
+    assert_eq!(Supported::RoundRobin, round_robin.format);
+    // 9 players: everyone against everyone
+    assert_eq!(36., round_robin.matches);
+    assert_eq!(9., round_robin.rounds);
+}
@@ -0,0 +1,232 @@
+use super::structs::{Branch, Duel};
+
+/// maximum width of a name in a [`Bracket`], longer ones are cut
+const NAME_WIDTH: usize = 24;
+/// lines between the first lines of two [`Duel`]s in the first round
+const PITCH: usize = 4;
+
+/// the bracket of a [`Branch`]: every round of it is a column of [`Duel`]s
+///
+/// a round is treated as the continuation of the previous one if it has half as many [`Duel`]s,
+/// in this case it's [`Duel`]s are drawn in between the ones they're waiting for,
+/// otherwise they're just listed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bracket<'a> {
+    /// [`Duel`]s by rounds, byes last in every round
+    rounds: Vec<Vec<&'a Duel>>,
+}
+
+/// lines of a [`Duel`] in a [`Bracket`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Place {
+    /// line of the homie
+    pub top: usize,
+    /// line of the id, and the one leading on to the next round
+    pub mid: usize,
+    /// line of the guest
+    pub bottom: usize,
+}
+
+impl<'a> Bracket<'a> {
+    /// the [`Bracket`] of `branch` made up of `matches`: played ones, pending ones and byes
+    pub fn new(matches: &'a [Duel], branch: Branch) -> Self {
+        let mut matches = matches
+            .iter()
+            .filter(|duel| duel.id.branch == branch)
+            .collect::<Vec<_>>();
+        // byes: number 0, so they get to the end of their round
+        matches.sort_by_key(|duel| (duel.id.round, duel.id.nr == 0, duel.id.nr));
+
+        let mut rounds: Vec<Vec<&Duel>> = Vec::new();
+        for duel in matches {
+            match rounds.last_mut() {
+                Some(round) if round[0].id.round == duel.id.round => round.push(duel),
+                _ => rounds.push(vec![duel]),
+            }
+        }
+        Self { rounds }
+    }
+    /// there are no [`Duel`]s in `self`
+    pub fn is_empty(&self) -> bool {
+        self.rounds.is_empty()
+    }
+    /// [`Duel`]s by rounds, byes last in every round
+    pub fn rounds(&self) -> &[Vec<&'a Duel>] {
+        &self.rounds
+    }
+    /// the `i`th round is a continuation of the previous one: it's [`Duel`]s are waiting for those
+    pub fn continues(&self, i: usize) -> bool {
+        i > 0
+            && self.rounds[i - 1].len() > 1
+            && self.rounds[i].len() == self.rounds[i - 1].len().div_ceil(2)
+    }
+    /// where the [`Duel`]s shall be drawn, by rounds
+    pub fn places(&self) -> Vec<Vec<Place>> {
+        let mut places: Vec<Vec<Place>> = Vec::new();
+        for (i, round) in self.rounds.iter().enumerate() {
+            let column = (0..round.len())
+                .map(|j| {
+                    if !self.continues(i) {
+                        let top = j * PITCH;
+                        return Place {
+                            top,
+                            mid: top + 1,
+                            bottom: top + 2,
+                        };
+                    }
+                    let prev = &places[i - 1];
+                    let top = prev[2 * j].mid;
+                    let bottom = prev.get(2 * j + 1).map_or(top + 2, |p| p.mid);
+                    Place {
+                        top,
+                        mid: (top + bottom) / 2,
+                        bottom,
+                    }
+                })
+                .collect();
+            places.push(column);
+        }
+        places
+    }
+    /// width of a name in `self`
+    fn name_width(&self) -> usize {
+        self.rounds
+            .iter()
+            .flatten()
+            .flat_map(|duel| [&duel.homie, &duel.guest])
+            .filter(|player| !player.is_unset())
+            .map(|player| player.to_string().chars().count())
+            .max()
+            .unwrap_or_default()
+            .clamp(4, NAME_WIDTH)
+    }
+    /// draw `self` with box-drawing characters into pages, each fitting into `width` columns
+    pub fn render(&self, width: usize) -> Vec<String> {
+        let name_width = self.name_width();
+        // name, space, mark, space, the bracket itself: `─┐` and the line leading on: `──`
+        let column_width = name_width + 7;
+        let per_page = (width / column_width).max(1);
+        let places = self.places();
+
+        (0..self.rounds.len())
+            .step_by(per_page)
+            .map(|first| {
+                let last = (first + per_page).min(self.rounds.len());
+                let height = places[first..last]
+                    .iter()
+                    .flatten()
+                    .map(|p| p.bottom + 1)
+                    .max()
+                    .unwrap_or_default();
+                let mut canvas = vec![vec![' '; (last - first) * column_width]; height];
+
+                for (i, places) in places.iter().enumerate().take(last).skip(first) {
+                    let x = (i - first) * column_width;
+                    let leads_on = i + 1 < self.rounds.len() && self.continues(i + 1);
+                    for (duel, place) in self.rounds[i].iter().zip(places) {
+                        draw_duel(&mut canvas, x, name_width, duel, *place, leads_on);
+                    }
+                }
+
+                let header = (first..last)
+                    .map(|i| {
+                        let title = self.rounds[i][0].id.to_string();
+                        let title = title.split('-').next().unwrap_or_default();
+                        format!("{title:<column_width$}")
+                    })
+                    .collect::<String>();
+                let lines = canvas
+                    .into_iter()
+                    .map(|line| line.into_iter().collect::<String>().trim_end().to_string());
+                std::iter::once(header.trim_end().to_string())
+                    .chain(lines)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect()
+    }
+}
+
+/// draw `duel` to `canvas` at column `x` to lines of `place`
+///
+/// if `leads_on`, the line going out of it is drawn as well
+fn draw_duel(
+    canvas: &mut [Vec<char>],
+    x: usize,
+    name_width: usize,
+    duel: &Duel,
+    place: Place,
+    leads_on: bool,
+) {
+    let mut write = |y: usize, x: usize, s: &str| {
+        for (i, c) in s.chars().enumerate() {
+            canvas[y][x + i] = c;
+        }
+    };
+    let name = |player: &super::Player| {
+        let name = player.to_string();
+        if name.chars().count() > name_width {
+            let cut = name.chars().take(name_width - 1).collect::<String>();
+            format!("{cut}…")
+        } else {
+            name
+        }
+    };
+    let mark = |won: bool| if won { '✓' } else { ' ' };
+    let bye = duel.guest.is_unset();
+    let guest = if bye { "bye".into() } else { name(&duel.guest) };
+    let id = if bye {
+        String::new()
+    } else {
+        duel.id.to_string()
+    };
+
+    let homie = format!(
+        "{:<name_width$} {} ─┐",
+        name(&duel.homie),
+        mark(duel.outcome == Some(true))
+    );
+    let guest = format!(
+        "{guest:<name_width$} {} ─┘",
+        mark(duel.outcome == Some(false))
+    );
+    write(place.top, x, &homie);
+    write(place.bottom, x, &guest);
+    for y in place.top + 1..place.bottom {
+        write(y, x + name_width + 4, "│");
+    }
+    if place.mid != place.top && place.mid != place.bottom {
+        write(place.mid, x, &id);
+        write(
+            place.mid,
+            x + name_width + 4,
+            if leads_on { "├──" } else { "├" },
+        );
+    }
+}
+
+/// width of the terminal: `COLUMNS` environment variable, 80 if not set
+pub fn term_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|cols| cols.parse().ok())
+        .unwrap_or(80)
+}
+
+/// print brackets of `branches` made up of `matches`, fitting into the terminal
+pub fn print(matches: &[Duel], branches: &[(Branch, &str)]) {
+    let width = term_width();
+    for (branch, title) in branches {
+        let bracket = Bracket::new(matches, *branch);
+        if bracket.is_empty() {
+            continue;
+        }
+        println!("{title}:\n");
+        for page in bracket.render(width) {
+            println!("{page}\n");
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,55 @@
+use super::*;
+use crate::tournament::{MatchId, Player};
+use pretty_assertions::assert_eq;
+
+fn duel(round: usize, nr: usize, homie: &str, guest: &str, outcome: Option<bool>) -> Duel {
+    let player = |name: &str| Player {
+        name: name.into(),
+        class: None,
+        nr: 0,
+    };
+    let guest = if guest.is_empty() {
+        Player::default()
+    } else {
+        player(guest)
+    };
+    Duel {
+        id: MatchId::new(Branch::Round, round, nr),
+        ..Duel::new(player(homie), guest).with_outcome(outcome)
+    }
+}
+
+#[test]
+fn render() {
+    let matches = [
+        duel(1, 1, "Anna", "Bob", Some(true)),
+        duel(1, 2, "Cecil", "Dora", Some(false)),
+        duel(1, 0, "Emil", "", Some(true)),
+        duel(2, 1, "Anna", "Dora", None),
+        duel(2, 0, "Emil", "", Some(true)),
+    ];
+    let bracket = Bracket::new(&matches, Branch::Round);
+    let expected = [
+        "R1          R2",
+        "Anna  ✓ ─┐",
+        "R1-1     ├──Anna    ─┐",
+        "Bob     ─┘           │",
+        "            R2-1     ├",
+        "Cecil   ─┐           │",
+        "R1-2     ├──Dora    ─┘",
+        "Dora  ✓ ─┘",
+        "",
+        "Emil  ✓ ─┐",
+        "         ├──Emil  ✓ ─┐",
+        "bye     ─┘           ├",
+        "            bye     ─┘",
+    ]
+    .join("\n");
+    assert_eq!(vec![expected], bracket.render(80));
+
+    // one round on every page
+    let pages = bracket.render(20);
+    assert_eq!(2, pages.len());
+    assert!(pages[1].starts_with("R2\n"));
+    assert!(Bracket::new(&matches, Branch::Winner).is_empty());
+}
@@ -0,0 +1,198 @@
+use super::{Player, Players, Result, TournamentError};
+use std::io::Write;
+
+/// the commands understood at check-in
+pub const HELP: &str = "\
+commands:
+    <name>                   the player is present, eg: `anna`, `ka`, `anna 9a`
+    absent <name>            the player isn't present after all
+    add <name>[, <class>]    a walk-in: a new player, present, eg: `add Kovács Anna, 9A`
+    list                     every player, present or not
+    help                     this help
+    done                     start the tournament with the present players: no-shows are removed";
+
+/// what's been typed at check-in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// the name of the present player
+    Present(String),
+    /// the name of the absent player
+    Absent(String),
+    /// the walk-in player: `<name>[, <class>]`
+    Add(String),
+    List,
+    Help,
+    Done,
+}
+
+impl Command {
+    /// the [`Command`] in `input`, a [`Command::Present`] if it's none of the others
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+        let (command, rest) = input.split_once(' ').unwrap_or((input, ""));
+        let rest = rest.trim().into();
+        match command.to_lowercase().as_str() {
+            "absent" => Self::Absent(rest),
+            "add" => Self::Add(rest),
+            "list" => Self::List,
+            "help" | "?" => Self::Help,
+            "done" => Self::Done,
+            _ => Self::Present(input.into()),
+        }
+    }
+}
+
+/// marking who of the [`Players`] is present before the [`super::Tournament`] starts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckIn {
+    /// every player with whether they're present
+    players: Vec<(Player, bool)>,
+}
+
+impl CheckIn {
+    /// nobody's present yet
+    pub fn new(players: Players) -> Self {
+        Self {
+            players: players
+                .0
+                .into_iter()
+                .map(|player| (player, false))
+                .collect(),
+        }
+    }
+    /// execute what's been typed at check-in, see [`Command`]
+    ///
+    /// returns `false` if check-in is done
+    ///
+    /// # Errors
+    ///
+    /// if there are less than 3 present players when done, or the player can't be found or added
+    pub fn prompt(&mut self, input: &str) -> Result<bool> {
+        match Command::parse(input) {
+            Command::Present(name) => {
+                let player = self.mark(&name, true)?;
+                println!("present: {player}");
+            }
+            Command::Absent(name) => {
+                let player = self.mark(&name, false)?;
+                println!("absent: {player}");
+            }
+            Command::Add(input) => {
+                let player = self.add(&input)?;
+                println!("added: {player}");
+            }
+            Command::List => {
+                for (player, present) in &self.players {
+                    let mark = if *present { 'x' } else { ' ' };
+                    println!("    [{mark}] {player}");
+                }
+                println!(
+                    "{} of {} present",
+                    self.present().0.len(),
+                    self.players.len()
+                );
+            }
+            Command::Help => println!("{HELP}"),
+            Command::Done => {
+                let present = self.present().0.len();
+                if present < 3 {
+                    return Err(TournamentError::NotEnoughPlayers(present));
+                }
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+    /// mark the [`Player`] `input` refers to `present` or not
+    ///
+    /// `input`: parts of the name, class and number, see [`Player::fits`]
+    pub fn mark(&mut self, input: &str, present: bool) -> Result<&Player> {
+        let fitting = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, (player, _))| player.fits(input))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        // the one with exactly this name, if many fit
+        let exact = fitting
+            .iter()
+            .copied()
+            .filter(|&idx| self.players[idx].0.name.eq_ignore_ascii_case(input.trim()))
+            .collect::<Vec<_>>();
+        let idx = match (fitting.as_slice(), exact.as_slice()) {
+            ([], _) => return Err(TournamentError::InvalidInput("no such player")),
+            ([idx], _) | (_, [idx]) => *idx,
+            _ => {
+                return Err(TournamentError::InvalidInput(
+                    "ambiguous player, type more of the name",
+                ))
+            }
+        };
+        let (player, was_present) = &mut self.players[idx];
+        *was_present = present;
+        Ok(player)
+    }
+    /// add a walk-in [`Player`], present: `input` is `<name>[, <class>]`
+    ///
+    /// players of the same name and class are told apart by their number, see [`Players::number_duplicates`]
+    pub fn add(&mut self, input: &str) -> Result<&Player> {
+        let player = Player::try_from(input).map_err(TournamentError::InvalidInput)?;
+        self.players.push((player, true));
+        let mut players = Players(self.players.iter().map(|(p, _)| p.clone()).collect());
+        players.number_duplicates();
+        for ((player, _), numbered) in self.players.iter_mut().zip(players.0) {
+            *player = numbered;
+        }
+        Ok(&self.players[self.players.len() - 1].0)
+    }
+    /// the present [`Players`], in their original order, walk-ins last
+    pub fn present(&self) -> Players {
+        Players(
+            self.players
+                .iter()
+                .filter(|(_, present)| *present)
+                .map(|(player, _)| player.clone())
+                .collect(),
+        )
+    }
+    /// the [`Players`] who haven't shown up
+    pub fn absent(&self) -> Players {
+        Players(
+            self.players
+                .iter()
+                .filter(|(_, present)| !present)
+                .map(|(player, _)| player.clone())
+                .collect(),
+        )
+    }
+}
+
+/// check in `players` interactively: the present ones are returned once done, see [`CheckIn::prompt`]
+///
+/// the end of input is the same as being done
+pub fn run(players: Players) -> Result<Players> {
+    let mut check_in = CheckIn::new(players);
+    println!("\nCHECK-IN\n--------\n\n{HELP}\n");
+    loop {
+        print!("check-in (<name>, 'help' for commands): ");
+        std::io::stdout().flush()?;
+        let mut buf = String::new();
+        std::io::stdin().read_line(&mut buf)?;
+        let input = if buf.is_empty() { "done" } else { &buf };
+        match check_in.prompt(input) {
+            Ok(true) => {}
+            Ok(false) => break,
+            // nothing more to read: no use asking again
+            Err(e) if buf.is_empty() => return Err(e),
+            Err(e) => println!("{e}"),
+        }
+    }
+    for player in check_in.absent().0 {
+        println!("no-show, removed: {player}");
+    }
+    Ok(check_in.present())
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,58 @@
+use super::*;
+use crate::tournament::Class;
+
+#[test]
+fn parse() {
+    assert_eq!(Command::Present("anna".into()), Command::parse(" anna\n"));
+    assert_eq!(
+        Command::Absent("kovács anna".into()),
+        Command::parse("absent  kovács anna")
+    );
+    assert_eq!(
+        Command::Add("Bob, 9B".into()),
+        Command::parse("Add Bob, 9B")
+    );
+    assert_eq!(Command::Done, Command::parse("done"));
+}
+
+#[test]
+fn check_in() {
+    let players = ["Anna", "Johan", "Bob", "Cecil"]
+        .map(|name| Player::new(name, Class::new(9, 'A')))
+        .to_vec();
+    let mut check_in = CheckIn::new(Players(players.clone()));
+
+    assert!(check_in.prompt("anna").unwrap());
+    assert!(check_in.prompt("bob").unwrap());
+    assert!(check_in.prompt("nobody").is_err());
+    assert!(
+        check_in.prompt("done").is_err(),
+        "not enough present players"
+    );
+    assert!(check_in.prompt("cecil").unwrap());
+    assert!(check_in.prompt("absent cecil").unwrap());
+    assert!(check_in.prompt("add Anna, 9A").unwrap());
+    assert!(check_in.prompt("add , 9A").is_err());
+    assert!(check_in.prompt("add Dora").unwrap());
+    assert!(!check_in.prompt("done").unwrap());
+
+    let anna = |nr| Player {
+        nr,
+        ..players[0].clone()
+    };
+    let dora = Player {
+        name: "Dora".into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        Players(vec![anna(1), players[2].clone(), anna(2), dora]),
+        check_in.present()
+    );
+    assert_eq!(
+        Players(vec![players[1].clone(), players[3].clone()]),
+        check_in.absent()
+    );
+    // "an" fits both Annas and Johan
+    assert!(check_in.mark("an", true).is_err());
+    assert_eq!(&anna(2), check_in.mark("anna #2", false).unwrap());
+}
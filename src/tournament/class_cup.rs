@@ -0,0 +1,169 @@
+use super::{
+    format::Standing,
+    structs::{Class, Player},
+};
+
+/// points for the final places, the first one for the winner: places after the last one get nothing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Points(pub Vec<usize>);
+
+impl Default for Points {
+    fn default() -> Self {
+        Self(vec![10, 8, 6, 5, 4, 3, 2, 1])
+    }
+}
+
+impl std::str::FromStr for Points {
+    type Err = String;
+
+    /// points separated by commas, eg: `10,8,6,5,4,3,2,1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|points| {
+                points
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid points '{points}', expected eg: '10,8,6'"))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Points {
+    /// points for `place`, starting from 1
+    pub fn of(&self, place: usize) -> usize {
+        place
+            .checked_sub(1)
+            .and_then(|idx| self.0.get(idx))
+            .copied()
+            .unwrap_or_default()
+    }
+    /// points for each of `count` players sharing `place`: the points of the places they cover, split evenly
+    pub fn shared(&self, place: usize, count: usize) -> f64 {
+        let covered = (place..place + count)
+            .map(|place| self.of(place))
+            .sum::<usize>();
+        covered as f64 / count.max(1) as f64
+    }
+}
+
+/// a [`Class`]'s place in the [`ClassCup`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassScore {
+    /// starting from 1, shared by classes of equal points
+    pub place: usize,
+    pub class: Class,
+    /// sum of the points of it's players
+    pub points: f64,
+    /// number of it's players
+    pub players: usize,
+}
+
+/// ranking of the classes by the final places of their players
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassCup {
+    /// best first
+    pub scores: Vec<ClassScore>,
+}
+
+impl ClassCup {
+    /// the class cup from `standings`, players get `points` for their place,
+    /// players sharing a place split the points of the places they cover, see [`Points::shared`]
+    ///
+    /// participants made up of `members`, eg: pairs of doubles or teams, split their points among them,
+    /// players without a class are left out
+    pub fn new(
+        standings: &[Standing],
+        points: &Points,
+        members: impl Fn(&Player) -> Vec<Player>,
+    ) -> Self {
+        let mut scores: Vec<ClassScore> = Vec::new();
+        for standing in standings {
+            let sharing = standings
+                .iter()
+                .filter(|other| other.place == standing.place)
+                .count();
+            let members = members(&standing.player);
+            let share = points.shared(standing.place, sharing) / members.len().max(1) as f64;
+            for class in members.iter().filter_map(|member| member.class) {
+                let idx = scores
+                    .iter()
+                    .position(|score| score.class == class)
+                    .unwrap_or_else(|| {
+                        scores.push(ClassScore {
+                            place: 0,
+                            class,
+                            points: 0.,
+                            players: 0,
+                        });
+                        scores.len() - 1
+                    });
+                scores[idx].points += share;
+                scores[idx].players += 1;
+            }
+        }
+        // stable: the class of the better player first on equal points
+        scores.sort_by(|a, b| b.points.total_cmp(&a.points));
+        let all = scores.iter().map(|score| score.points).collect::<Vec<_>>();
+        for score in &mut scores {
+            score.place = all.iter().filter(|points| **points > score.points).count() + 1;
+        }
+        Self { scores }
+    }
+    /// header and rows of `self`: place, class, points, players
+    fn rows(&self) -> Vec<[String; 4]> {
+        let header = ["Place", "Class", "Points", "Players"].map(String::from);
+        let rows = self.scores.iter().map(|score| {
+            [
+                score.place.to_string(),
+                score.class.to_string(),
+                score.points.to_string(),
+                score.players.to_string(),
+            ]
+        });
+        std::iter::once(header).chain(rows).collect()
+    }
+    /// `self` as aligned text for the terminal
+    pub fn render(&self) -> String {
+        self.rows()
+            .iter()
+            .map(|[place, class, points, players]| {
+                format!("{place:>5}  {class:<5}  {points:>6}  {players:>7}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    /// `self` as a markdown table
+    pub fn markdown(&self) -> String {
+        let mut rows = self
+            .rows()
+            .into_iter()
+            .map(|row| format!("| {} |", row.join(" | ")));
+        let mut lines = rows.next().into_iter().collect::<Vec<_>>();
+        lines.push("| --: | --- | --: | --: |".into());
+        lines.extend(rows);
+        lines.join("\n") + "\n"
+    }
+    /// write `self` as csv to `writer`
+    pub fn write_csv(&self, writer: impl std::io::Write) -> csv::Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        for row in self.rows() {
+            writer.write_record(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+    /// save `self` to `path`: a markdown table if it ends with `.md`, csv otherwise
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|ext| ext == "md") {
+            std::fs::write(path, self.markdown())
+        } else {
+            Ok(self.write_csv(std::fs::File::create(path)?)?)
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
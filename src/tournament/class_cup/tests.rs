@@ -0,0 +1,98 @@
+use super::*;
+use crate::tournament::Player;
+
+#[test]
+fn class_cup() {
+    let class = |grade| Class::new(grade, 'A');
+    let standing = |place, name: &str, class: Option<Class>| Standing {
+        place,
+        player: Player {
+            name: name.into(),
+            class,
+            nr: 0,
+        },
+        note: String::new(),
+    };
+    let standings = [
+        standing(1, "Anna", Some(class(9))),
+        standing(2, "Bob", Some(class(10))),
+        standing(3, "Cecil", Some(class(10))),
+        standing(3, "Dora", None),
+        standing(5, "Emil", Some(class(9))),
+        standing(5, "Fred", Some(class(11))),
+        standing(5, "Gert", Some(class(11))),
+        standing(5, "Hugo", Some(class(11))),
+    ];
+    let points = "10, 8, 6, 5, 4".parse().unwrap();
+    // the 3rd and 4th places split by Cecil and Dora, the 5th to the 8th by four of them
+    assert_eq!(5.5, Points::shared(&points, 3, 2));
+    assert_eq!(1., Points::shared(&points, 5, 4));
+    let cup = ClassCup::new(&standings, &points, |player| vec![player.clone()]);
+    let scores = cup
+        .scores
+        .iter()
+        .map(|score| (score.place, score.class, score.points, score.players))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (1, class(10), 13.5, 2),
+            (2, class(9), 11., 2),
+            (3, class(11), 3., 3),
+        ],
+        scores
+    );
+    assert_eq!(
+        "| Place | Class | Points | Players |\n| --: | --- | --: | --: |\n| 1 | 10A | 13.5 | 2 |",
+        cup.markdown()
+            .lines()
+            .take(3)
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    assert!("10,x".parse::<Points>().is_err());
+    assert_eq!(0, Points::default().of(9));
+}
+
+#[test]
+fn pairs() {
+    let member = |name: &str, class: Option<Class>| Player {
+        name: name.into(),
+        class,
+        nr: 0,
+    };
+    let (nine, ten) = (Class::new(9, 'A'), Class::new(10, 'A'));
+    let pairs = [
+        (
+            "Anna & Bob",
+            [member("Anna", Some(nine)), member("Bob", Some(ten))],
+        ),
+        (
+            "Cecil & Dora",
+            [member("Cecil", Some(ten)), member("Dora", None)],
+        ),
+    ];
+    let standings = pairs
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| Standing {
+            place: i + 1,
+            player: member(name, None),
+            note: String::new(),
+        })
+        .collect::<Vec<_>>();
+    let members = |pair: &Player| {
+        pairs
+            .iter()
+            .find(|(name, _)| *name == pair.name)
+            .map(|(_, members)| members.to_vec())
+            .unwrap_or_default()
+    };
+    // the points of a pair are split between it's players, the class-less one's left out
+    let cup = ClassCup::new(&standings, &"10, 8".parse().unwrap(), members);
+    let scores = cup
+        .scores
+        .iter()
+        .map(|score| (score.place, score.class, score.points, score.players))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(1, ten, 9., 2), (2, nine, 5., 1)], scores);
+}
@@ -0,0 +1,185 @@
+use super::{
+    format::Standing,
+    structs::{Duel, Player},
+};
+
+/// result of a [`Player`] against another one in a [`Crosstable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Won,
+    Lost,
+    /// they're playing right now
+    Pending,
+    /// they haven't met yet
+    Empty,
+    /// a player against themself
+    Itself,
+}
+
+/// every [`Player`] against every other, the usual way of publishing round-robin results
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crosstable {
+    pub players: Vec<Player>,
+    /// `cells[i][j]`: result of `players[i]` against `players[j]`
+    pub cells: Vec<Vec<Cell>>,
+    /// number of won duels of `players`
+    pub wins: Vec<usize>,
+    /// points of `players`: one for every won duel
+    pub points: Vec<usize>,
+    /// place of `players`, shared by the ones with equal points
+    pub places: Vec<usize>,
+}
+
+impl Crosstable {
+    /// crosstable of `players` from `matches`: played and pending [`Duel`]s
+    pub fn new(players: Vec<Player>, matches: &[Duel]) -> Self {
+        let idx = |player: &Player| players.iter().position(|p| p == player);
+        let mut cells = (0..players.len())
+            .map(|i| {
+                (0..players.len())
+                    .map(|j| if i == j { Cell::Itself } else { Cell::Empty })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for duel in matches {
+            let (Some(homie), Some(guest)) = (idx(&duel.homie), idx(&duel.guest)) else {
+                continue;
+            };
+            let (homie_cell, guest_cell) = match duel.outcome {
+                Some(true) => (Cell::Won, Cell::Lost),
+                Some(false) => (Cell::Lost, Cell::Won),
+                None => (Cell::Pending, Cell::Pending),
+            };
+            cells[homie][guest] = homie_cell;
+            cells[guest][homie] = guest_cell;
+        }
+
+        let wins = cells
+            .iter()
+            .map(|row| row.iter().filter(|cell| **cell == Cell::Won).count())
+            .collect::<Vec<_>>();
+        let points = wins.clone();
+        let places = points
+            .iter()
+            .map(|p| points.iter().filter(|other| *other > p).count() + 1)
+            .collect();
+
+        Self {
+            players,
+            cells,
+            wins,
+            points,
+            places,
+        }
+    }
+    /// crosstable of the players of `standings` in their order, from `matches`: byes are ignored
+    pub fn from_standings(standings: &[Standing], matches: &[Duel]) -> Self {
+        let players = standings.iter().map(|s| s.player.clone()).collect();
+        let played = matches
+            .iter()
+            .filter(|duel| !duel.guest.is_unset())
+            .cloned()
+            .collect::<Vec<_>>();
+        Self::new(players, &played)
+    }
+    /// header and rows of `self`: number, player, results against each other, wins, points, place
+    fn rows(&self) -> Vec<Vec<String>> {
+        let header = ["#".to_string(), "Player".into()]
+            .into_iter()
+            .chain((1..=self.players.len()).map(|i| i.to_string()))
+            .chain(["Wins".into(), "Points".into(), "Place".into()]);
+        let rows = self.players.iter().enumerate().map(|(i, player)| {
+            [(i + 1).to_string(), player.to_string()]
+                .into_iter()
+                .chain(self.cells[i].iter().map(|cell| cell.symbol().to_string()))
+                .chain([
+                    self.wins[i].to_string(),
+                    self.points[i].to_string(),
+                    self.places[i].to_string(),
+                ])
+                .collect()
+        });
+        std::iter::once(header.collect()).chain(rows).collect()
+    }
+    /// `self` as aligned text for the terminal
+    pub fn render(&self) -> String {
+        let rows = self.rows();
+        let widths = (0..rows[0].len())
+            .map(|col| {
+                rows.iter()
+                    .map(|row| row[col].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        rows.iter()
+            .map(|row| {
+                let line = row
+                    .iter()
+                    .zip(&widths)
+                    .enumerate()
+                    .map(|(col, (text, width))| {
+                        // names to the left, everything else to the right
+                        if col == 1 {
+                            format!("{text:<width$}")
+                        } else {
+                            format!("{text:>width$}")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    /// `self` as a markdown table
+    pub fn markdown(&self) -> String {
+        let rows = self.rows();
+        let escape = |text: &String| text.replace('|', "\\|");
+        let align = (0..rows[0].len())
+            .map(|col| match col {
+                0 => "--:",
+                1 => "---",
+                col if col < 2 + self.players.len() => ":-:",
+                _ => "--:",
+            })
+            .collect::<Vec<_>>();
+        let line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+        let mut lines = vec![line(rows[0].iter().map(escape).collect())];
+        lines.push(line(align.into_iter().map(String::from).collect()));
+        lines.extend(
+            rows[1..]
+                .iter()
+                .map(|row| line(row.iter().map(escape).collect())),
+        );
+        lines.join("\n") + "\n"
+    }
+    /// write `self` as csv to `writer`
+    pub fn write_csv(&self, writer: impl std::io::Write) -> csv::Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        for row in self.rows() {
+            writer.write_record(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Cell {
+    /// short form of `self`
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Won => "1",
+            Self::Lost => "0",
+            Self::Pending => "?",
+            Self::Empty => "",
+            Self::Itself => "x",
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,55 @@
+use super::*;
+use crate::tournament::players::tests::nu_p;
+
+#[test]
+fn new() {
+    let players = vec![
+        nu_p("Anna", 9, 'A'),
+        nu_p("Bob", 9, 'B'),
+        nu_p("Cecil", 10, 'A'),
+    ];
+    let duel = |i: usize, j: usize, outcome| {
+        Duel::new(players[i].clone(), players[j].clone()).with_outcome(outcome)
+    };
+    let matches = [
+        duel(0, 1, Some(false)),
+        duel(2, 0, Some(false)),
+        duel(1, 2, None),
+    ];
+    let table = Crosstable::new(players.clone(), &matches);
+
+    use Cell::*;
+    assert_eq!(
+        table.cells,
+        [
+            [Itself, Lost, Won],
+            [Won, Itself, Pending],
+            [Lost, Pending, Itself]
+        ]
+    );
+    assert_eq!(table.wins, [1, 1, 0]);
+    assert_eq!(table.places, [1, 1, 3]);
+
+    let expected = [
+        "#  Player      1  2  3  Wins  Points  Place",
+        "1  Anna, 9A    x  0  1     1       1      1",
+        "2  Bob, 9B     1  x  ?     1       1      1",
+        "3  Cecil, 10A  0  ?  x     0       0      3",
+    ]
+    .join("\n");
+    assert_eq!(expected, table.render());
+
+    let expected = "\
+| # | Player | 1 | 2 | 3 | Wins | Points | Place |
+| --: | --- | :-: | :-: | :-: | --: | --: | --: |
+| 1 | Anna, 9A | x | 0 | 1 | 1 | 1 | 1 |
+| 2 | Bob, 9B | 1 | x | ? | 1 | 1 | 1 |
+| 3 | Cecil, 10A | 0 | ? | x | 0 | 0 | 3 |
+";
+    assert_eq!(expected, table.markdown());
+
+    let mut csv = Vec::new();
+    table.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("#,Player,1,2,3,Wins,Points,Place\n1,\"Anna, 9A\",x,0,1,1,1,1\n"));
+}
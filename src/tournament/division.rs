@@ -0,0 +1,191 @@
+use super::{format::Supported, players::Row, Players, Result, Start, TournamentError};
+use std::path::{Path, PathBuf};
+
+/// how [`Players`] are split into divisions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// by the `division` column of the file with participants
+    Column,
+    /// by the grade of their [`super::Class`]: every inclusive range is a division, named like `5-8`
+    Grades(Vec<(u8, u8)>),
+}
+
+impl std::str::FromStr for Rule {
+    type Err = String;
+
+    /// `column`, or grade ranges separated by commas, eg: `5-8,9-12`, a single grade is a range too: `5-8,9`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("column") {
+            return Ok(Self::Column);
+        }
+        let grade = |grade: &str| {
+            grade.trim().parse::<u8>().map_err(|_| {
+                format!("invalid grade '{grade}', expected eg: 'column' or '5-8,9-12'")
+            })
+        };
+        s.split(',')
+            .map(|range| match range.split_once('-') {
+                Some((from, to)) => Ok((grade(from)?, grade(to)?)),
+                None => grade(range).map(|grade| (grade, grade)),
+            })
+            .collect::<std::result::Result<_, _>>()
+            .map(Self::Grades)
+    }
+}
+
+/// the format of a division on the command line: `<DIVISION>=<FORMAT>`
+pub fn parse_format(s: &str) -> std::result::Result<(String, Supported), String> {
+    use clap::ValueEnum;
+    let (division, format) = s
+        .split_once('=')
+        .ok_or("expected <DIVISION>=<FORMAT>, eg: '5-8=round-robin'")?;
+    let format = Supported::from_str(format.trim(), true)?;
+    Ok((division.trim().into(), format))
+}
+
+/// [`Players`] playing an independent [`Tournament`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Division {
+    pub name: String,
+    /// the rows of it's players in the file with participants
+    pub rows: Vec<Row>,
+}
+
+impl Division {
+    /// the players of `self`
+    pub fn players(&self) -> Players {
+        Players(self.rows.iter().map(|row| row.player.clone()).collect())
+    }
+}
+
+/// split the `rows` of players into divisions by `rule`, in the order of the rule
+/// or the one the divisions first appear in
+///
+/// returns the divisions and the players in none of them
+pub fn split(rows: Vec<Row>, rule: &Rule) -> (Vec<Division>, Players) {
+    let mut divisions = match rule {
+        Rule::Column => Vec::new(),
+        Rule::Grades(ranges) => ranges
+            .iter()
+            .map(|&(from, to)| Division {
+                name: if from == to {
+                    from.to_string()
+                } else {
+                    format!("{from}-{to}")
+                },
+                rows: Vec::new(),
+            })
+            .collect(),
+    };
+    let mut left_out = Players::default();
+    for row in rows {
+        let idx = match rule {
+            Rule::Column => row.division.clone().map(|name| {
+                divisions
+                    .iter()
+                    .position(|division| division.name == name)
+                    .unwrap_or_else(|| {
+                        divisions.push(Division {
+                            name,
+                            rows: Vec::new(),
+                        });
+                        divisions.len() - 1
+                    })
+            }),
+            Rule::Grades(ranges) => row.player.class.and_then(|class| {
+                ranges
+                    .iter()
+                    .position(|(from, to)| (from..=to).contains(&&class.grade))
+            }),
+        };
+        match idx {
+            Some(idx) => divisions[idx].rows.push(row),
+            None => left_out.0.push(row.player),
+        }
+    }
+    (divisions, left_out)
+}
+
+/// `path` with the name of the `division` added to it's file name, eg: `log-5-8.csv`
+fn for_division(path: &Path, division: &str) -> PathBuf {
+    let suffix = division
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{suffix}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{suffix}"),
+    };
+    path.with_file_name(name)
+}
+
+/// run the divisions of the participants in the file of `args` one after the other by `rule`,
+/// every one of them in it's own format, `args.format` by default, saving to their own files
+pub fn execute(args: crate::args::Args, rule: &Rule) -> Result<()> {
+    let Some(file) = &args.file else {
+        return Err(std::io::Error::other("no file with participants given").into());
+    };
+    let rows = Players::load_rows(file, args.strict)?;
+    let (divisions, left_out) = split(rows, rule);
+    for player in left_out.0 {
+        eprintln!("warning: {player} isn't in any division, left out");
+    }
+    if let Some((name, _)) = args
+        .division_format
+        .iter()
+        .find(|(name, _)| divisions.iter().all(|division| division.name != *name))
+    {
+        eprintln!("warning: there's no division '{name}'");
+    }
+
+    for division in divisions {
+        let format = args
+            .division_format
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == division.name)
+            .map_or(args.format, |(_, format)| *format);
+        let (players, pairs) = super::participants(division.rows, &args)?;
+        println!(
+            "\n\n\n\nDIVISION {}: {} participants, {}\n",
+            division.name,
+            players.0.len(),
+            format.name()
+        );
+        let file = |path: &Option<PathBuf>| {
+            path.as_deref()
+                .map(|path| for_division(path, &division.name))
+        };
+        let args = crate::args::Args {
+            format,
+            log: file(&args.log),
+            crosstable: file(&args.crosstable),
+            class_cup_file: file(&args.class_cup_file),
+            html: file(&args.html),
+            svg: file(&args.svg),
+            svg_empty: file(&args.svg_empty),
+            ..args.clone()
+        };
+        match format.run(Start {
+            players,
+            pairs,
+            args,
+        }) {
+            // the other divisions are independent of it
+            Err(TournamentError::NotEnoughPlayers(n)) => eprintln!(
+                "warning: division {} isn't played: {n} participants",
+                division.name
+            ),
+            Err(e @ TournamentError::InvalidCount(_)) => {
+                eprintln!("warning: division {} isn't played: {e}", division.name)
+            }
+            res => res?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,74 @@
+use super::*;
+use crate::tournament::{Class, Player};
+
+#[test]
+fn rule() {
+    assert_eq!(Ok(Rule::Column), "Column".parse());
+    assert_eq!(Ok(Rule::Grades(vec![(5, 8), (9, 12)])), "5-8, 9-12".parse());
+    assert_eq!(Ok(Rule::Grades(vec![(5, 8), (9, 9)])), "5-8,9".parse());
+    assert!("5-x".parse::<Rule>().is_err());
+    assert_eq!(
+        Ok(("lower".into(), Supported::RoundRobin)),
+        parse_format("lower=round-robin")
+    );
+    assert!(parse_format("lower").is_err());
+}
+
+#[test]
+fn split() {
+    let player = |name: &str, grade| Player::new(name, Class::new(grade, 'A'));
+    let row = |player, division: Option<&str>| Row {
+        player,
+        division: division.map(String::from),
+        partner: None,
+    };
+    let rows = vec![
+        row(player("Anna", 5), Some("girls")),
+        row(player("Bob", 10), Some("boys")),
+        row(player("Cecil", 12), None),
+        row(Player::default(), Some("girls")),
+    ];
+    let (divisions, left_out) = super::split(rows.clone(), &Rule::Column);
+    let names = divisions
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["girls", "boys"], names);
+    assert_eq!(
+        Players(vec![player("Anna", 5), Player::default()]),
+        divisions[0].players()
+    );
+    assert_eq!(Players(vec![player("Cecil", 12)]), left_out);
+
+    let (divisions, left_out) = super::split(rows.clone(), &"5-8,9-11".parse().unwrap());
+    assert_eq!(
+        vec![
+            Division {
+                name: "5-8".into(),
+                rows: vec![rows[0].clone()],
+            },
+            Division {
+                name: "9-11".into(),
+                rows: vec![rows[1].clone()],
+            },
+        ],
+        divisions
+    );
+    // no class: no grade
+    assert_eq!(
+        Players(vec![player("Cecil", 12), Player::default()]),
+        left_out
+    );
+}
+
+#[test]
+fn for_division() {
+    assert_eq!(
+        PathBuf::from("out/log-upper-grades.csv"),
+        super::for_division("out/log.csv".as_ref(), "Upper Grades")
+    );
+    assert_eq!(
+        PathBuf::from("table-5-8"),
+        super::for_division("table".as_ref(), "5-8")
+    );
+}
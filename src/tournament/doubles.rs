@@ -0,0 +1,139 @@
+use super::{players::Row, Player, Players, Result};
+use crate::rating::{self, Ratings};
+use std::{collections::HashMap, path::Path};
+
+/// how the pairs of a doubles event are made up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Pairing {
+    /// by the `partner` and `partner_class` columns of the file with participants
+    Partner,
+    /// randomly
+    Random,
+    /// by the ratings: the best player with the worst one, so that pairs are about as strong
+    Balanced,
+}
+
+/// the individual players of the pairs of a doubles event, or of drawn teams, by the participant they make up
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pairs(pub HashMap<Player, Vec<Player>>);
+
+impl Pairs {
+    /// add the pair, or team of `members`: a participant named after all of them, eg: `Anna, 9A & Bob, 10B`
+    ///
+    /// returns the participant
+    pub fn add(&mut self, members: Vec<Player>) -> Player {
+        let name = members
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" & ");
+        let participant = Player {
+            name,
+            class: None,
+            nr: 0,
+        };
+        self.0.insert(participant.clone(), members);
+        participant
+    }
+    /// the individual players of `participant`: just itself if it's not a pair
+    pub fn members(&self, participant: &Player) -> Vec<Player> {
+        match self.0.get(participant) {
+            Some(members) => members.clone(),
+            None => vec![participant.clone()],
+        }
+    }
+    /// make up pairs of the players of `rows` by `pairing`, `ratings` are needed for [`Pairing::Balanced`]
+    ///
+    /// returns the participants: the pairs, `self` and the players left without a partner
+    pub fn make(
+        rows: Vec<Row>,
+        pairing: Pairing,
+        ratings: &Ratings,
+        config: &rating::Config,
+    ) -> (Players, Self, Players) {
+        let mut pairs = Self::default();
+        let mut participants = Players::default();
+        let mut players = rows
+            .iter()
+            .map(|row| row.player.clone())
+            .collect::<Vec<_>>();
+        let mut left_out = Players::default();
+        match pairing {
+            Pairing::Partner => {
+                let mut paired: Vec<Player> = Vec::new();
+                for row in rows {
+                    if paired.contains(&row.player) {
+                        continue;
+                    }
+                    let Some(partner) = row.partner else {
+                        left_out.0.push(row.player);
+                        continue;
+                    };
+                    // the partner's own row, if there's one
+                    let partner = players
+                        .iter()
+                        .find(|p| {
+                            p.name == partner.name
+                                && partner.class.is_none_or(|class| p.class == Some(class))
+                                && !paired.contains(p)
+                        })
+                        .cloned()
+                        .unwrap_or(partner);
+                    if partner == row.player {
+                        left_out.0.push(row.player);
+                        continue;
+                    }
+                    paired.extend([row.player.clone(), partner.clone()]);
+                    participants.0.push(pairs.add(vec![row.player, partner]));
+                }
+                // the ones named as a partner by someone else
+                left_out.0.retain(|player| !paired.contains(player));
+            }
+            Pairing::Random | Pairing::Balanced => {
+                if pairing == Pairing::Random {
+                    fastrand::shuffle(&mut players);
+                } else {
+                    ratings.sort(&mut players, config);
+                }
+                // the middle one if there's an odd number of players: the best one stays with the worst one
+                if players.len() % 2 == 1 {
+                    left_out.0.push(players.remove(players.len() / 2));
+                }
+                while players.len() >= 2 {
+                    let first = players.remove(0);
+                    let second = match pairing {
+                        Pairing::Balanced => players.pop(),
+                        _ => Some(players.remove(0)),
+                    };
+                    if let Some(second) = second {
+                        participants.0.push(pairs.add(vec![first, second]));
+                    }
+                }
+            }
+        }
+        (participants, pairs, left_out)
+    }
+}
+
+/// make up pairs of the players of `rows` by `pairing`, ratings are read from `ratings` or `ratings.csv`
+///
+/// players left without a partner are printed as warnings
+pub fn pair_up(
+    rows: Vec<Row>,
+    pairing: Pairing,
+    ratings: Option<&Path>,
+    config: &rating::Config,
+) -> Result<(Players, Pairs)> {
+    let ratings = match pairing {
+        Pairing::Balanced => Ratings::load(ratings.unwrap_or("ratings.csv".as_ref()))?,
+        _ => Ratings::default(),
+    };
+    let (participants, pairs, left_out) = Pairs::make(rows, pairing, &ratings, config);
+    for player in left_out.0 {
+        eprintln!("warning: {player} has got no partner, left out");
+    }
+    Ok((participants, pairs))
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,96 @@
+use super::*;
+use crate::{rating::Rating, tournament::Class};
+
+fn player(name: &str) -> Player {
+    Player::new(name, Class::new(9, 'A'))
+}
+
+#[test]
+fn partner() {
+    let row = |name: &str, partner: Option<&str>| Row {
+        player: player(name),
+        partner: partner.map(|name| Player {
+            name: name.into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let rows = vec![
+        row("Anna", Some("Bob")),
+        row("Bob", Some("Anna")),
+        row("Cecil", None),
+        row("Dora", Some("Emil")),
+        row("Fred", None),
+        row("Gert", Some("Fred")),
+    ];
+    let (participants, pairs, left_out) = Pairs::make(
+        rows,
+        Pairing::Partner,
+        &Ratings::default(),
+        &Default::default(),
+    );
+    let names = participants
+        .0
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "Anna, 9A & Bob, 9A",
+            "Dora, 9A & Emil",
+            "Gert, 9A & Fred, 9A"
+        ],
+        names
+    );
+    assert_eq!(Players(vec![player("Cecil")]), left_out);
+    assert_eq!(
+        vec![player("Anna"), player("Bob")],
+        pairs.members(&participants.0[0])
+    );
+    assert_eq!(vec![player("Cecil")], pairs.members(&player("Cecil")));
+}
+
+#[test]
+fn balanced() {
+    let config = rating::Config::default();
+    let names = ["Anna", "Bob", "Cecil", "Dora", "Emil"];
+    let ratings = Ratings(
+        names
+            .iter()
+            .zip([1400., 1800., 1500., 1600., 1700.])
+            .map(|(name, rating)| Rating {
+                name: name.to_string(),
+                class: None,
+                rating,
+                deviation: 350.,
+                volatility: 0.06,
+                games: 0,
+            })
+            .collect(),
+    );
+    let rows = names
+        .iter()
+        .map(|name| Row {
+            player: player(name),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    let (participants, pairs, left_out) =
+        Pairs::make(rows.clone(), Pairing::Balanced, &ratings, &config);
+    // the best with the worst, the middle one's left out
+    assert_eq!(Players(vec![player("Dora")]), left_out);
+    assert_eq!(
+        vec![
+            vec![player("Bob"), player("Anna")],
+            vec![player("Emil"), player("Cecil")]
+        ],
+        participants
+            .0
+            .iter()
+            .map(|p| pairs.members(p))
+            .collect::<Vec<_>>()
+    );
+
+    let (participants, _, left_out) = Pairs::make(rows, Pairing::Random, &ratings, &config);
+    assert_eq!((2, 1), (participants.0.len(), left_out.0.len()));
+}
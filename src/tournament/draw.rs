@@ -0,0 +1,107 @@
+use super::{doubles::Pairs, Player, Players, Result};
+use crate::rating::{self, Ratings};
+use std::path::Path;
+
+/// what drawn teams are balanced by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Balance {
+    /// the ratings of the players
+    Rating,
+    /// the grades of the classes of the players: the higher the stronger
+    Grade,
+}
+
+/// draw teams of `size` from `players`, balanced by `balance`, `ratings` are needed for [`Balance::Rating`]
+///
+/// players are drawn tier by tier: the strongest ones first, every team gets one of every tier,
+/// the weakest team so far choosing first. a team chooses the strongest one left of the tier,
+/// of equally strong ones the one least similar to it's players by their classes, see [`Players::difference`]
+///
+/// returns the participants: the teams, their members and the players left out, randomly,
+/// if they can't make up a whole team
+pub fn draw(
+    players: Players,
+    size: usize,
+    balance: Balance,
+    ratings: &Ratings,
+    config: &rating::Config,
+) -> (Players, Pairs, Players) {
+    let strength = |player: &Player| match balance {
+        Balance::Rating => ratings.rating_of(player, config),
+        Balance::Grade => player.class.map_or(0., |class| f64::from(class.grade)),
+    };
+    let mut players = players.0;
+    // equally strong ones in random order
+    fastrand::shuffle(&mut players);
+    let count = players.len() / size.max(1);
+    let left_out = Players(players.split_off(count * size));
+    if count == 0 {
+        return (Players::default(), Pairs::default(), left_out);
+    }
+    players.sort_by(|a, b| strength(b).total_cmp(&strength(a)));
+
+    // the members of the teams and their total strength
+    let mut teams = vec![(Vec::new(), 0_f64); count];
+    for tier in players.chunks(count) {
+        let mut tier = tier.to_vec();
+        let mut order = (0..count).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| teams[a].1.total_cmp(&teams[b].1));
+        for team in order {
+            let (members, total) = &mut teams[team];
+            let difference = |player: &Player| {
+                members
+                    .iter()
+                    .map(|member| Players::difference(member, player))
+                    .min()
+                    .unwrap_or(u8::MAX)
+            };
+            let idx = (0..tier.len())
+                .max_by(|&a, &b| {
+                    let (a_player, b_player) = (&tier[a], &tier[b]);
+                    strength(a_player)
+                        .total_cmp(&strength(b_player))
+                        .then_with(|| difference(a_player).cmp(&difference(b_player)))
+                        .then(b.cmp(&a))
+                })
+                .unwrap_or_default();
+            *total += strength(&tier[idx]);
+            members.push(tier.remove(idx));
+        }
+    }
+    let mut pairs = Pairs::default();
+    let participants = teams
+        .into_iter()
+        .map(|(members, _)| pairs.add(members))
+        .collect();
+    (Players(participants), pairs, left_out)
+}
+
+/// draw teams of `size` from `players` by `balance`, ratings are read from `ratings` or `ratings.csv`,
+/// see [`draw`]
+///
+/// the teams are printed, players left out as warnings
+pub fn draw_teams(
+    players: Players,
+    size: usize,
+    balance: Balance,
+    ratings: Option<&Path>,
+    config: &rating::Config,
+) -> Result<(Players, Pairs)> {
+    let ratings = match balance {
+        Balance::Rating => Ratings::load(ratings.unwrap_or("ratings.csv".as_ref()))?,
+        Balance::Grade => Ratings::default(),
+    };
+    let (teams, pairs, left_out) = draw(players, size, balance, &ratings, config);
+    for player in left_out.0 {
+        eprintln!("warning: {player} isn't in any team, left out");
+    }
+    println!("TEAMS\n");
+    for (i, team) in teams.0.iter().enumerate() {
+        println!("{:>3}. {team}", i + 1);
+    }
+    println!();
+    Ok((teams, pairs))
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,86 @@
+use super::*;
+use crate::{rating::Rating, tournament::Class};
+
+#[test]
+fn draw() {
+    let config = rating::Config::default();
+    let players = [
+        ("Anna", 9, 'A'),
+        ("Bob", 9, 'A'),
+        ("Cecil", 9, 'A'),
+        ("Dora", 10, 'B'),
+        ("Emil", 10, 'B'),
+        ("Fred", 11, 'C'),
+        ("Gert", 11, 'C'),
+    ]
+    .map(|(name, grade, id)| Player::new(name, Class::new(grade, id)));
+    // the order of the players: Anna's the best
+    let ratings = Ratings(
+        players
+            .iter()
+            .zip((0..7).map(|i| 1900. - 100. * f64::from(i)))
+            .map(|(player, rating)| Rating {
+                name: player.name.clone(),
+                class: player.class,
+                rating,
+                deviation: 350.,
+                volatility: 0.06,
+                games: 0,
+            })
+            .collect(),
+    );
+
+    let total = |members: &[Player], balance| {
+        members
+            .iter()
+            .map(|player| match balance {
+                Balance::Rating => ratings.rating_of(player, &config),
+                Balance::Grade => player.class.map_or(0., |class| f64::from(class.grade)),
+            })
+            .sum::<f64>()
+    };
+    let spread = |teams: &Players, pairs: &Pairs, balance| {
+        let totals = teams
+            .0
+            .iter()
+            .map(|team| total(&pairs.members(team), balance))
+            .collect::<Vec<_>>();
+        let most = totals.iter().copied().fold(f64::MIN, f64::max);
+        let least = totals.iter().copied().fold(f64::MAX, f64::min);
+        most - least
+    };
+
+    let six = Players(players[..6].to_vec());
+    let (teams, pairs, left_out) = super::draw(six, 3, Balance::Rating, &ratings, &config);
+    assert!(left_out.0.is_empty());
+    let members = teams
+        .0
+        .iter()
+        .map(|team| pairs.members(team))
+        .collect::<Vec<_>>();
+    // Bob, the weaker one, chooses first in the second tier: Cecil
+    let [anna, bob, cecil, dora, emil, fred, _] = players.clone();
+    assert_eq!(
+        vec![vec![anna, dora, emil], vec![bob, cecil, fred]],
+        members
+    );
+    assert!(spread(&teams, &pairs, Balance::Rating) <= 100.);
+
+    let all = Players(players.to_vec());
+    let (teams, pairs, left_out) = super::draw(all, 2, Balance::Grade, &ratings, &config);
+    assert_eq!((3, 1), (teams.0.len(), left_out.0.len()));
+    assert!(spread(&teams, &pairs, Balance::Grade) <= 1.);
+
+    // equally strong ones: different classes in every team
+    let tens = ["Dora", "Emil", "Hugo", "Ida"]
+        .into_iter()
+        .zip(['B', 'B', 'D', 'D'])
+        .map(|(name, id)| Player::new(name, Class::new(10, id)))
+        .collect();
+    let (teams, pairs, _) = super::draw(Players(tens), 2, Balance::Grade, &ratings, &config);
+    for team in &teams.0 {
+        let members = pairs.members(team);
+        assert_eq!(2, members.len());
+        assert_ne!(members[0].class, members[1].class);
+    }
+}
@@ -0,0 +1,77 @@
+use super::{Diagnostic, MatchId};
+
+/// the result of loading, building and playing a [`super::Tournament`]
+pub type Result<T> = std::result::Result<T, TournamentError>;
+
+/// everything that can go wrong with a [`super::Tournament`]
+#[derive(Debug)]
+pub enum TournamentError {
+    /// a [`super::Tournament`] needs at least 3 participants: the number of them
+    NotEnoughPlayers(usize),
+    /// the format can't be played with the number of participants, see [`super::format::Format::check_count`]
+    InvalidCount(&'static str),
+    /// the [`super::Duel`] with this id hasn't got an outcome yet
+    NoOutcome(MatchId),
+    /// problems found in the file with participants, see [`super::Players::check`]
+    InvalidParticipants(Vec<Diagnostic>),
+    /// a result couldn't be entered, see [`super::Duel::find_outcome`]
+    InvalidResult(&'static str),
+    /// a command at the prompt couldn't be executed, see [`super::prompt::Command`]
+    InvalidInput(&'static str),
+    /// a player couldn't enter the running tournament, see [`super::format::Format::enter_late`]
+    LateEntry(&'static str),
+    /// the user's quit at the prompt, after everything's been saved: nothing more shall be done
+    Quit,
+    /// reading or writing a file
+    Io(std::io::Error),
+    /// reading or writing a csv file
+    Csv(csv::Error),
+}
+
+impl std::fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughPlayers(n) => write!(
+                f,
+                "you need at least 3 participants to play a tournament, got {n}"
+            ),
+            Self::InvalidCount(e) => write!(f, "invalid number of participants: {e}"),
+            Self::NoOutcome(id) => write!(f, "match {id} hasn't got an outcome yet"),
+            Self::InvalidParticipants(diagnostics) => {
+                write!(f, "problems in the file with participants:")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n    {diagnostic}")?;
+                }
+                Ok(())
+            }
+            Self::InvalidResult(e) => write!(f, "invalid result: {e}"),
+            Self::InvalidInput(e) => write!(f, "invalid input: {e}"),
+            Self::LateEntry(e) => write!(f, "late entry is impossible: {e}"),
+            Self::Quit => write!(f, "quit"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Csv(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for TournamentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Csv(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TournamentError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<csv::Error> for TournamentError {
+    fn from(e: csv::Error) -> Self {
+        Self::Csv(e)
+    }
+}
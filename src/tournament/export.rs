@@ -0,0 +1,38 @@
+use super::svg::Paper;
+use std::path::PathBuf;
+
+/// the files the state of a [`super::Tournament`] is saved to while it's running
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Export {
+    /// path of the html page regenerated after every result
+    pub html: Option<PathBuf>,
+    /// path of the svg of the brackets regenerated after every result
+    pub svg: Option<PathBuf>,
+    /// path of the empty svg of the brackets written before the first round
+    pub svg_empty: Option<PathBuf>,
+    /// paper the svg is made for
+    pub paper: Paper,
+    /// the svg is made for landscape paper
+    pub landscape: bool,
+    /// path of the csv file the log is saved to
+    pub log: Option<PathBuf>,
+    /// path of the file the crosstable is saved to
+    pub crosstable: Option<PathBuf>,
+    /// path of the file the class cup is saved to
+    pub class_cup: Option<PathBuf>,
+}
+
+impl From<&crate::args::Args> for Export {
+    fn from(args: &crate::args::Args) -> Self {
+        Self {
+            html: args.html.clone(),
+            svg: args.svg.clone(),
+            svg_empty: args.svg_empty.clone(),
+            paper: args.paper,
+            landscape: args.landscape,
+            log: args.log.clone(),
+            crosstable: args.crosstable.clone(),
+            class_cup: args.class_cup_file.clone(),
+        }
+    }
+}
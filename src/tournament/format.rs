@@ -1,14 +1,31 @@
 use super::{
+    bracket,
+    crosstable::Crosstable,
+    error::{Result, TournamentError},
+    heat::Heat,
     players::Players,
-    structs::{Duel, Player},
+    structs::{Branch, Duel, MatchId, Player},
 };
 
+/// `println!` unless `quiet`, see [`Format::set_quiet`]
+macro_rules! say {
+    ($quiet:expr, $($arg:tt)*) => {
+        if !$quiet {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub use americano::Americano;
 pub use double_elimination::DoubleElimination;
+pub use heats::Heats;
 pub use round_robin::RoundRobin;
 pub use single_elimination::SingleElimination;
 pub use swiss_system::SwissSystem;
 
+pub mod americano;
 pub mod double_elimination;
+pub mod heats;
 pub mod round_robin;
 pub mod single_elimination;
 pub mod swiss_system;
@@ -19,6 +36,37 @@ pub enum Supported {
     DoubleElimination,
     RoundRobin,
     SwissSystem,
+    /// doubles, every player partners every other once
+    Americano,
+    /// doubles, partners by the standings
+    Mexicano,
+}
+impl Supported {
+    /// the name of `self` on the command line
+    pub fn name(self) -> String {
+        use clap::ValueEnum;
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+    /// `run` with a new [`Format`] of `self`: the one place every supported format is made up
+    pub fn run<R: Run>(self, run: R) -> R::Output {
+        match self {
+            Self::SingleElimination => run.run(SingleElimination::default()),
+            Self::DoubleElimination => run.run(DoubleElimination::default()),
+            Self::RoundRobin => run.run(RoundRobin::default()),
+            Self::SwissSystem => run.run(SwissSystem::default()),
+            Self::Americano => run.run(Americano::default()),
+            Self::Mexicano => run.run(Americano::mexicano()),
+        }
+    }
+}
+
+/// something done with a [`Format`], whichever [`Supported`] one it is, see [`Supported::run`]
+pub trait Run {
+    type Output;
+    /// do it with `format`
+    fn run<F: Format>(self, format: F) -> Self::Output;
 }
 // impl Supported {
 //     pub fn to_format(self) -> Box<dyn Format> {
@@ -32,21 +80,208 @@ pub enum Supported {
 // }
 
 /// a format in which a [`super::Tournament`] shall be made
-pub trait Format {
+///
+/// a round is made up of stages, every stage consists of [`Duel`]s independent of each other,
+/// so their results may be entered in any order
+pub trait Format: Clone {
     /// add `players` to `self`
     /// shall be used for initialization
     fn add_players(&mut self, players: Players);
+    /// add `players` ordered by their strength, best first: seeding
+    ///
+    /// by default they're put into the standard bracket positions, so the best ones meet as late as possible
+    fn add_seeded_players(&mut self, mut players: Players) {
+        players.seed();
+        self.add_players(players);
+    }
+    /// `self` can be played with `count` participants, at least 3 of them
+    ///
+    /// # Errors
+    ///
+    /// if it can't, by default it can with any number of them
+    fn check_count(&self, _count: usize) -> Result<()> {
+        Ok(())
+    }
     /// shuffle players
     /// should be used on initialization
     fn initial_shuffle(&mut self) {}
     /// has the tournament reached to an end?
     fn is_end(&self) -> bool;
-    /// play the next round duels
+    /// the pending [`Duel`]s of the actual stage, byes excluded
+    ///
+    /// their whole [`MatchId`]s are given by the structure of the format, so they're known in advance,
+    /// eg: for printed brackets
     ///
-    /// if `standard`, then the original order is preserved, otherwise players are shuffled after every round
-    fn play_round(&mut self, standard: bool);
+    /// the next stage is started if the actual one has been applied,
+    /// `None` if there are no more stages in this round
+    ///
+    /// if `shuffle`, players are shuffled before making up new [`Duel`]s
+    fn next_duels(&mut self, shuffle: bool) -> Option<Vec<Duel>>;
+    /// apply the outcomes of `duels`: the ones returned by [`Self::next_duels`], all settled
+    ///
+    /// # Errors
+    ///
+    /// if any of them hasn't got an outcome, `self` is left unchanged then
+    fn apply(&mut self, duels: Vec<Duel>) -> Result<()>;
+    /// `player` enters the already running tournament, playing from the next round on
+    ///
+    /// the rounds missed count as lost, the policy is up to the format,
+    /// returns the [`Duel`]s of the rounds already started `player` has lost by walkover
+    ///
+    /// # Errors
+    ///
+    /// by default, as the format doesn't support late entries
+    fn enter_late(&mut self, _player: Player) -> Result<Vec<Duel>> {
+        Err(TournamentError::LateEntry("not supported by the format"))
+    }
+    /// the individual players of `participant`, eg: partners made up by the format,
+    /// by default just itself
+    fn members(&self, participant: &Player) -> Vec<Player> {
+        vec![participant.clone()]
+    }
+    /// [`Player`]s of the actual stage who've got no opponent: they get a bye
+    fn byes(&self) -> Vec<Player> {
+        Vec::new()
+    }
+    /// `self` doesn't print what's happening if `quiet`, eg: when it's played out in advance,
+    /// by default it doesn't print anything anyway
+    fn set_quiet(&mut self, _quiet: bool) {}
+    /// the matches of the actual stage of any number of players: heats, by default there are none
+    ///
+    /// the [`Duel`]s of every pair of the players of a heat share it's id, see [`Heat::duels`]
+    fn heats(&self) -> Vec<Heat> {
+        Vec::new()
+    }
+    /// the [`Branch`]es of the bracket with their titles, empty if `self` has no bracket
+    fn branches(&self) -> &'static [(Branch, &'static str)] {
+        &[]
+    }
+    /// the actual standings, best first
+    fn standings(&self) -> Vec<Standing>;
     /// print the actual status
-    fn print_status(&self);
+    ///
+    /// `matches`: every [`Duel`] so far: played, pending ones and byes
+    fn print_status(&self, matches: &[Duel]);
     /// results in reversed order
     fn results(self) -> Players;
 }
+
+/// a [`Player`]'s place in the standings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    /// starting from 1, shared by players not yet separable
+    pub place: usize,
+    pub player: Player,
+    /// short info, eg: points, the branch the player is in
+    pub note: String,
+}
+
+/// standings from `groups` of [`Player`]s with a note, best first
+///
+/// [`Player`]s of a group share their place
+pub(super) fn standings(groups: impl IntoIterator<Item = (Vec<Player>, String)>) -> Vec<Standing> {
+    let mut standings = Vec::new();
+    for (players, note) in groups {
+        let place = standings.len() + 1;
+        standings.extend(players.into_iter().map(|player| Standing {
+            place,
+            player,
+            note: note.clone(),
+        }));
+    }
+    standings
+}
+
+/// standings from `players` with their points, best first: [`Player`]s of equal points share their place
+fn by_points(players: Vec<(Player, u8)>) -> Vec<Standing> {
+    let mut groups: Vec<(Vec<Player>, String)> = Vec::new();
+    for (player, points) in players {
+        let note = format!("{points} points");
+        match groups.last_mut() {
+            Some((group, last)) if *last == note => group.push(player),
+            _ => groups.push((vec![player], note)),
+        }
+    }
+    standings(groups)
+}
+
+/// knocked out [`Player`]s, one per group, as the last one is the best
+fn knocked_groups(knocked: &Players) -> impl Iterator<Item = (Vec<Player>, String)> + '_ {
+    knocked
+        .0
+        .iter()
+        .rev()
+        .map(|player| (vec![player.clone()], "knocked out".into()))
+}
+
+/// the [`Player`]s of `duels`, byes excluded
+fn players_of(duels: Option<&Vec<Duel>>) -> Vec<Player> {
+    duels
+        .into_iter()
+        .flatten()
+        .flat_map(|duel| [&duel.homie, &duel.guest])
+        .filter(|player| !player.is_unset())
+        .cloned()
+        .collect()
+}
+
+/// is `duel` a bye: does it lack any of its players
+fn is_bye(duel: &Duel) -> bool {
+    duel.homie.is_unset() || duel.guest.is_unset()
+}
+
+/// the [`Player`]s of `duels` without an opponent
+fn byes(duels: Option<&Vec<Duel>>) -> Vec<Player> {
+    duels
+        .into_iter()
+        .flatten()
+        .filter(|duel| is_bye(duel))
+        .map(|duel| duel.homie.clone())
+        .filter(|player| !player.is_unset())
+        .collect()
+}
+
+/// give ids to the playable [`Duel`]s of `duels` in the order they shall be played: `first`,
+/// then the following numbers in it's round, and return copies of them
+fn pending(duels: &mut [Duel], first: MatchId) -> Vec<Duel> {
+    duels
+        .iter_mut()
+        .rev()
+        .filter(|duel| !is_bye(duel))
+        .enumerate()
+        .map(|(i, duel)| {
+            duel.id = MatchId {
+                nr: first.nr + i,
+                ..first
+            };
+            duel.clone()
+        })
+        .collect()
+}
+
+/// set outcomes of `duels` from the ones in `played` with the same [`MatchId`]
+///
+/// # Errors
+///
+/// if any of `duels` apart from byes is left without an outcome, `duels` are left unchanged then
+fn settle(duels: &mut [Duel], played: Vec<Duel>) -> Result<()> {
+    // the one played last with it's id, it's own otherwise
+    let outcome = |duel: &Duel| {
+        played
+            .iter()
+            .rev()
+            .find(|played| played.id == duel.id)
+            .map_or(duel.outcome, |played| played.outcome)
+    };
+    // nothing's changed if any of them is left without an outcome
+    if let Some(duel) = duels
+        .iter()
+        .find(|duel| !is_bye(duel) && outcome(duel).is_none())
+    {
+        return Err(TournamentError::NoOutcome(duel.id));
+    }
+    for duel in duels.iter_mut().filter(|duel| !is_bye(duel)) {
+        duel.outcome = outcome(duel);
+    }
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use super::*;
+use crate::tournament::doubles::Pairs;
+use std::collections::HashMap;
+
+/// how the partners of the rounds of an [`Americano`] are chosen
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Partners {
+    /// americano: every player partners every other once, by the circle method of [`RoundRobin`]
+    #[default]
+    Everyone,
+    /// mexicano: by the actual standings, of every four players the first and the fourth
+    /// play against the second and the third
+    ByStandings,
+}
+
+#[derive(Default, PartialEq, Eq, Clone, Debug)]
+/// doubles with partners changing every round: americano or mexicano, see [`Partners`]
+///
+/// every player's got their own points: one for every win, none for sitting out a round
+pub struct Americano {
+    pub partners: Partners,
+    /// all the participating [`Players`], in the order of their seed
+    pub players: Players,
+    /// points of the `players`
+    pub points: HashMap<Player, u8>,
+    /// the number of `round`s already executed
+    pub round: usize,
+    /// the number of rounds to be played: enough for every player to partner every other once
+    pub rounds: usize,
+    /// the partners of every round of an americano: the [`Duel`]s of it's rounds
+    circle: RoundRobin,
+    /// every pair made up so far, with it's players
+    pairs: Pairs,
+    /// [`Player`]s sitting out the actual round
+    resting: Vec<Player>,
+    /// [`Player`]s who've sat out a round, once for every round
+    rested: Vec<Player>,
+    /// [`Duel`]s of the pairs of the actual round
+    duels: Option<Vec<Duel>>,
+    /// the round's [`Duel`]s have been applied
+    applied: bool,
+    /// doesn't print what's happening, see [`Format::set_quiet`]
+    quiet: bool,
+}
+
+impl Americano {
+    /// a mexicano: partners by the actual standings
+    pub fn mexicano() -> Self {
+        Self {
+            partners: Partners::ByStandings,
+            ..Default::default()
+        }
+    }
+    /// the actual ranking: by points, then by seed
+    fn ranking(&self) -> Vec<Player> {
+        let mut ranking = self.players.0.clone();
+        // stable: seed order on equal points
+        ranking.sort_by_key(|player| std::cmp::Reverse(self.points[player]));
+        ranking
+    }
+    /// the partners of the next round, every two following pairs play against each other,
+    /// and the [`Player`]s sitting it out
+    fn partners(&self) -> (Vec<(Player, Player)>, Vec<Player>) {
+        let mut pairs = Vec::new();
+        let mut resting = Vec::new();
+        match self.partners {
+            Partners::Everyone => {
+                for duel in self.circle.duels_of(self.round) {
+                    match (duel.homie.is_unset(), duel.guest.is_unset()) {
+                        (false, false) => pairs.push((duel.homie, duel.guest)),
+                        // the partner of the bye
+                        (true, _) => resting.push(duel.guest),
+                        (_, true) => resting.push(duel.homie),
+                    }
+                }
+                // even, as the number of players is checked, see `Format::check_count`
+            }
+            Partners::ByStandings => {
+                let mut ranking = self.ranking();
+                // the ones who've sat out the fewest rounds, the lower ranked ones first
+                let rested = |player: &Player| self.rested.iter().filter(|p| *p == player).count();
+                for _ in 0..ranking.len() % 4 {
+                    let idx = (0..ranking.len())
+                        .rev()
+                        .min_by_key(|&idx| rested(&ranking[idx]))
+                        .unwrap_or_default();
+                    resting.push(ranking.remove(idx));
+                }
+                for four in ranking.chunks_exact(4) {
+                    pairs.push((four[0].clone(), four[3].clone()));
+                    pairs.push((four[1].clone(), four[2].clone()));
+                }
+            }
+        }
+        (pairs, resting)
+    }
+    /// the [`Duel`]s of the pairs of the upcoming round, setting the [`Player`]s sitting it out
+    pub fn gen_duels(&mut self) -> Vec<Duel> {
+        let (partners, resting) = self.partners();
+        self.resting = resting;
+        let pairs = partners
+            .into_iter()
+            .map(|(a, b)| self.pairs.add(vec![a, b]))
+            .collect::<Vec<_>>();
+        // the first court shall be the first match: `pending` numbers them from the back
+        pairs
+            .chunks_exact(2)
+            .rev()
+            .map(|pairs| Duel::new(pairs[0].clone(), pairs[1].clone()))
+            .collect()
+    }
+}
+
+impl Format for Americano {
+    fn add_players(&mut self, players: Players) {
+        self.circle = RoundRobin::default();
+        self.circle.add_players(players.clone());
+        self.rounds = self.circle.len() - 1;
+        self.points = players.0.iter().map(|p| (p.clone(), 0)).collect();
+        self.players = players;
+    }
+
+    /// an americano: every player partners every other once, two partnerships in every duel,
+    /// so their number has to be even: it is if there are `4k` or `4k + 1` players,
+    /// a mexicano: a court of two pairs
+    fn check_count(&self, count: usize) -> Result<()> {
+        if count < 4 {
+            return Err(TournamentError::InvalidCount(
+                "at least 4 players are needed for a court of two pairs",
+            ));
+        }
+        if self.partners == Partners::Everyone && count % 4 > 1 {
+            return Err(TournamentError::InvalidCount(
+                "in an americano every player partners every other once, \
+                that's possible with 4, 5, 8, 9, 12, 13... players, try mexicano",
+            ));
+        }
+        Ok(())
+    }
+
+    fn initial_shuffle(&mut self) {
+        fastrand::shuffle(&mut self.players.0);
+        self.circle.add_players(self.players.clone());
+    }
+
+    fn is_end(&self) -> bool {
+        self.round == self.rounds && self.duels.is_none()
+    }
+
+    fn next_duels(&mut self, _: bool) -> Option<Vec<Duel>> {
+        if self.applied {
+            self.applied = false;
+            return None;
+        }
+        if self.duels.is_none() {
+            self.duels = Some(self.gen_duels());
+            if !self.resting.is_empty() {
+                let resting = self
+                    .resting
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                say!(self.quiet, "\nsitting out: {}\n", resting.join(", "));
+            }
+        }
+        let first = MatchId::new(Branch::Round, self.round + 1, 1);
+        self.duels.as_mut().map(|duels| pending(duels, first))
+    }
+
+    fn apply(&mut self, played: Vec<Duel>) -> Result<()> {
+        let Some(duels) = &mut self.duels else {
+            return Ok(());
+        };
+        settle(duels, played)?;
+        let duels = self.duels.take().unwrap_or_default();
+        for duel in duels {
+            let (winner, _loser) = duel.into_result()?;
+            // both partners get a point
+            for player in self.pairs.members(&winner) {
+                self.points.entry(player).and_modify(|p| *p += 1);
+            }
+        }
+        self.rested.append(&mut self.resting);
+        self.round += 1;
+        self.applied = true;
+        Ok(())
+    }
+
+    fn members(&self, participant: &Player) -> Vec<Player> {
+        self.pairs.members(participant)
+    }
+
+    fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+    fn standings(&self) -> Vec<Standing> {
+        let ranking = self
+            .ranking()
+            .into_iter()
+            .map(|player| {
+                let points = self.points[&player];
+                (player, points)
+            })
+            .collect();
+        by_points(ranking)
+    }
+
+    fn print_status(&self, _: &[Duel]) {
+        println!(
+            "\n\nSTANDINGS after round {} of {}:\n",
+            self.round, self.rounds
+        );
+        for standing in self.standings() {
+            println!(
+                "    {}. {}: {}",
+                standing.place, standing.player, standing.note
+            );
+        }
+        println!("\n\n\n");
+    }
+
+    fn results(self) -> Players {
+        let mut results = self.ranking();
+        results.reverse();
+        Players(results)
+    }
+}
@@ -6,6 +6,42 @@ pub struct DoubleElimination {
     winner_branch: Players,
     loser_branch: Players,
     knocked: Players,
+    /// players who've lost in the winner branch this round
+    new_losers: Players,
+    /// the number of the actual round, starting from 1: the one of the winner branch,
+    /// the loser branch has got two rounds in every one of them, see [`Self::first_id`]
+    pub round: usize,
+    /// the number of the last [`Duel`] of the actual round in the winner and the loser branch:
+    /// the special ones are numbered on from them
+    numbered: (usize, usize),
+    /// stage of the actual round
+    stage: Stage,
+    /// [`Duel`]s of the actual stage, byes included
+    duels: Option<Vec<Duel>>,
+    /// doesn't print what's happening, see [`Format::set_quiet`]
+    quiet: bool,
+}
+
+/// stages of a round of [`DoubleElimination`]
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+enum Stage {
+    /// the round hasn't started yet
+    #[default]
+    Idle,
+    /// duels of the winner branch
+    Winner,
+    /// new losers mixed into the previous loser branch
+    FirstLoser,
+    /// duels of the loser branch
+    SecondLoser,
+    /// only one player remained in both branches
+    Final,
+    /// uneven number of players in winner branch: special pre-match duel
+    SpecialWinner,
+    /// uneven number of players in loser branch: special pre-match duel
+    SpecialLoser,
+    /// the round is over
+    Done,
 }
 
 impl DoubleElimination {
@@ -14,80 +50,113 @@ impl DoubleElimination {
             winner_branch,
             loser_branch,
             knocked,
+            ..Default::default()
         }
     }
-    fn play_winner_branch(&mut self, shuffle: bool) -> (Players, Players) {
-        let (mut next_winner_b, mut next_loser_b) = (Players::default(), Players::default());
-        let mut winner_b = std::mem::take(&mut self.winner_branch).into_duels(shuffle);
-        // get outcomes for winner branch duels, move contestants to other branch if necessary
-        while let Some(w_duel) = winner_b.pop() {
-            // duel isn't ready yet to be played, waiting for opponent
-            if w_duel.guest.is_unset() {
-                next_winner_b.0.push(w_duel.homie); // should get into the next-round winner branch
-                continue;
+    /// prepare the [`Duel`]s of the actual stage
+    ///
+    /// `false` if the round is over
+    fn prepare(&mut self, shuffle: bool) -> bool {
+        match self.stage {
+            Stage::Idle => {
+                say!(self.quiet, "\nwinner duels:");
+                let winner_b = std::mem::take(&mut self.winner_branch);
+                self.duels = Some(winner_b.into_duels(shuffle));
+                self.stage = Stage::Winner;
+                self.round += 1;
+            }
+            // if previous loser branch has players
+            //     wait for new losers
+            //     mix them into the previous loser branch
+            //     execute those
+            //     and execute the result again
+            Stage::FirstLoser => {
+                let mut prev_loser_b = std::mem::take(&mut self.loser_branch);
+                if prev_loser_b.0.is_empty() {
+                    self.loser_branch.0.append(&mut self.new_losers.0);
+                    self.stage = Stage::SecondLoser;
+                    return self.prepare(shuffle);
+                }
+                // insertion idx
+                let mut i = 1;
+                // insert new losers into prev losers
+                while i <= prev_loser_b.0.len() && !self.new_losers.0.is_empty() {
+                    prev_loser_b.0.insert(i, self.new_losers.0.remove(0));
+                    i += 2;
+                }
+                say!(self.quiet, "\nloser duels:");
+                self.duels = Some(prev_loser_b.into_duels(shuffle));
+            }
+            Stage::SecondLoser => {
+                say!(self.quiet, "\nsecond-round loser duels:");
+                let temp_loser_b = std::mem::take(&mut self.loser_branch);
+                self.duels = Some(temp_loser_b.into_duels(shuffle));
+            }
+            Stage::Done => {
+                self.stage = Stage::Idle;
+                self.numbered = (0, 0);
+                return false;
             }
-            println!("\nwinner duel: {w_duel}");
-            // play the duel, that leads us to having the result
-            let (winner, loser) = w_duel.play();
-            next_winner_b.0.push(winner); // winner get's to winner branch
-            next_loser_b.0.push(loser); // loser get's to loser branch
+            // those are prepared when the previous stage is applied
+            Stage::Winner | Stage::Final | Stage::SpecialWinner | Stage::SpecialLoser => {}
         }
-        (next_winner_b, next_loser_b)
+        true
     }
-
-    // if previous loser branch has players
-    //     wait for new losers
-    //     mix them into the previous loser branch
-    //     execute those
-    //     and execute the result again
-    fn play_loser_branch(&mut self, next_loser_b: &mut Players, shuffle: bool) {
-        let mut prev_loser_b = std::mem::take(&mut self.loser_branch);
-
-        let mut temp_loser_b = Players::default();
-        if prev_loser_b.0.is_empty() {
-            temp_loser_b.0.append(&mut next_loser_b.0);
+    /// the [`MatchId`] of the first [`Duel`] of the actual stage
+    ///
+    /// the new losers mixed into the previous loser branch play the first round of the loser branch
+    /// in the actual round, the second one is played by the winners of that
+    fn first_id(&self) -> MatchId {
+        let (winner, loser) = self.numbered;
+        match self.stage {
+            Stage::Final => MatchId::new(Branch::Final, 1, 1),
+            Stage::SpecialWinner => MatchId::new(Branch::Winner, self.round, winner + 1),
+            Stage::FirstLoser => MatchId::new(Branch::Loser, 2 * self.round - 2, 1),
+            Stage::SecondLoser => MatchId::new(Branch::Loser, 2 * self.round - 1, 1),
+            Stage::SpecialLoser => MatchId::new(Branch::Loser, 2 * self.round - 1, loser + 1),
+            _ => MatchId::new(Branch::Winner, self.round, 1),
+        }
+    }
+    /// after the loser branch: final game or special duels if needed
+    fn after_loser_branch(&mut self) {
+        // final game: only player from winner and loser branch
+        if self.winner_branch.0.len() == 1 && self.new_losers.0.len() == 1 {
+            let homie = self.winner_branch.0.pop().unwrap();
+            let guest = self.new_losers.0.pop().unwrap();
+            say!(self.quiet, "FINAL GAME:");
+            self.duels = Some(vec![Duel::new(homie, guest)]);
+            self.stage = Stage::Final;
         } else {
-            // insertion idx
-            let mut i = 1;
-            // insert new losers into prev losers
-            while i <= prev_loser_b.0.len() && !next_loser_b.0.is_empty() {
-                prev_loser_b.0.insert(i, next_loser_b.0.remove(0));
-                i += 2;
-            }
-
-            let mut first_loser_d = prev_loser_b.into_duels(shuffle);
-            // get outcomes for loser branch duels, move contestants to other branch if necessary
-            while let Some(l_duel) = first_loser_d.pop() {
-                // duel isn't ready yet to be played, waiting for opponent
-                if l_duel.guest.is_unset() {
-                    temp_loser_b.0.push(l_duel.homie); // should get into the next-round loser branch
-                    continue;
-                }
-                println!("\nloser duel: {l_duel}");
-                // play the duel, that leads us to having the result
-                let (winner, loser) = l_duel.play();
-                temp_loser_b.0.push(winner); // winner get's to loser branch
-                println!("bye-bye {loser}");
-                self.knocked.0.push(loser); // loser get's knocked out of the tournament
-            }
+            self.after_final();
         }
-
-        let mut second_loser_d = temp_loser_b.into_duels(shuffle);
-        // get outcomes for loser branch duels, move contestants to other branch if necessary
-        while let Some(l_duel) = second_loser_d.pop() {
-            // duel isn't ready yet to be played, waiting for opponent
-            if l_duel.guest.is_unset() {
-                next_loser_b.0.push(l_duel.homie); // should get into the next-round loser branch
-                continue;
-            }
-            println!("\nsecond-round loser duel: {l_duel}");
-            // play the duel, that leads us to having the result
-            let (winner, loser) = l_duel.play();
-            next_loser_b.0.push(winner); // winner get's to loser branch
-            println!("bye-bye {loser}");
-            self.knocked.0.push(loser); // loser get's knocked out of the tournament
+    }
+    /// after the final game: special duels if needed
+    fn after_final(&mut self) {
+        // uneven number of players: we need a special pre-match-duel
+        if self.winner_branch.0.len() != 1 && self.winner_branch.0.len() % 2 == 1 {
+            say!(self.quiet, "\nspecial winner duel:");
+            self.duels = Some(vec![Duel::special(&mut self.winner_branch)]);
+            self.stage = Stage::SpecialWinner;
+        } else {
+            self.after_special_winner();
         }
     }
+    /// after the special winner duel: special loser duel if needed
+    fn after_special_winner(&mut self) {
+        // uneven number of players: we need a special pre-match-duel
+        if self.new_losers.0.len() != 1 && self.new_losers.0.len() % 2 == 1 {
+            say!(self.quiet, "\nspecial loser duel:");
+            self.duels = Some(vec![Duel::special(&mut self.new_losers)]);
+            self.stage = Stage::SpecialLoser;
+        } else {
+            self.finish_round();
+        }
+    }
+    /// and we apply the changes by turning new losers into the loser branch
+    fn finish_round(&mut self) {
+        self.loser_branch = std::mem::take(&mut self.new_losers);
+        self.stage = Stage::Done;
+    }
 }
 
 impl Format for DoubleElimination {
@@ -99,57 +168,154 @@ impl Format for DoubleElimination {
     }
 
     fn is_end(&self) -> bool {
-        self.winner_branch.0.is_empty() && self.loser_branch.0.is_empty()
+        self.winner_branch.0.is_empty() && self.loser_branch.0.is_empty() && self.duels.is_none()
     }
 
-    fn play_round(&mut self, standard: bool) {
-        let (mut next_winner_b, mut next_loser_b) = self.play_winner_branch(!standard);
-        println!("\n-----------------------------");
+    fn next_duels(&mut self, shuffle: bool) -> Option<Vec<Duel>> {
+        loop {
+            if self.duels.is_none() && !self.prepare(shuffle) {
+                return None;
+            }
+            let first = self.first_id();
+            let pending = pending(self.duels.as_mut()?, first);
+            if let Some(last) = pending.last() {
+                match self.stage {
+                    Stage::Winner => self.numbered.0 = last.id.nr,
+                    Stage::SecondLoser => self.numbered.1 = last.id.nr,
+                    _ => {}
+                }
+                return Some(pending);
+            }
+            self.apply(Vec::new()).ok()?;
+        }
+    }
 
-        self.play_loser_branch(&mut next_loser_b, !standard);
+    fn apply(&mut self, played: Vec<Duel>) -> Result<()> {
+        let Some(duels) = &mut self.duels else {
+            return Ok(());
+        };
+        settle(duels, played)?;
+        let mut duels = self.duels.take().unwrap_or_default();
 
-        // final game: only player from winner and loser branch
-        if next_winner_b.0.len() == 1 && next_loser_b.0.len() == 1 {
-            let homie = next_winner_b.0.pop().unwrap();
-            let guest = next_loser_b.0.pop().unwrap();
-            let finals = Duel::new(homie, guest);
-            println!("FINAL GAME: {finals}");
-            let (winner, second) = finals.play();
-            // NOTE: everyone get's to the knocked players' list,
-            // as it turns into the leaderboard if reversed
-            self.knocked.0.push(second);
-            self.knocked.0.push(winner);
+        match self.stage {
+            Stage::Winner => {
+                // get outcomes for winner branch duels, move contestants to other branch if necessary
+                while let Some(w_duel) = duels.pop() {
+                    // duel isn't ready yet to be played, waiting for opponent
+                    if w_duel.guest.is_unset() {
+                        self.winner_branch.0.push(w_duel.homie); // should get into the next-round winner branch
+                        continue;
+                    }
+                    let (winner, loser) = w_duel.into_result()?;
+                    self.winner_branch.0.push(winner); // winner get's to winner branch
+                    self.new_losers.0.push(loser); // loser get's to loser branch
+                }
+                say!(self.quiet, "\n-----------------------------");
+                self.stage = Stage::FirstLoser;
+            }
+            Stage::FirstLoser => {
+                // get outcomes for loser branch duels, move contestants to other branch if necessary
+                while let Some(l_duel) = duels.pop() {
+                    // duel isn't ready yet to be played, waiting for opponent
+                    if l_duel.guest.is_unset() {
+                        self.loser_branch.0.push(l_duel.homie); // should get into the next-round loser branch
+                        continue;
+                    }
+                    let (winner, loser) = l_duel.into_result()?;
+                    self.loser_branch.0.push(winner); // winner get's to loser branch
+                    say!(self.quiet, "bye-bye {loser}");
+                    self.knocked.0.push(loser); // loser get's knocked out of the tournament
+                }
+                self.stage = Stage::SecondLoser;
+            }
+            Stage::SecondLoser => {
+                // get outcomes for loser branch duels, move contestants to other branch if necessary
+                while let Some(l_duel) = duels.pop() {
+                    // duel isn't ready yet to be played, waiting for opponent
+                    if l_duel.guest.is_unset() {
+                        self.new_losers.0.push(l_duel.homie); // should get into the next-round loser branch
+                        continue;
+                    }
+                    let (winner, loser) = l_duel.into_result()?;
+                    self.new_losers.0.push(winner); // winner get's to loser branch
+                    say!(self.quiet, "bye-bye {loser}");
+                    self.knocked.0.push(loser); // loser get's knocked out of the tournament
+                }
+                self.after_loser_branch();
+            }
+            Stage::Final => {
+                let (winner, second) = duels.pop().unwrap().into_result()?;
+                // NOTE: everyone get's to the knocked players' list,
+                // as it turns into the leaderboard if reversed
+                self.knocked.0.push(second);
+                self.knocked.0.push(winner);
+                self.after_final();
+            }
+            Stage::SpecialWinner => {
+                let (winner, loser) = duels.pop().unwrap().into_result()?;
+                self.winner_branch.0.push(winner); // winner stays
+                self.new_losers.0.push(loser); // loser get's pushed to loser branch
+                self.after_special_winner();
+            }
+            Stage::SpecialLoser => {
+                let (winner, loser) = duels.pop().unwrap().into_result()?;
+                self.new_losers.0.push(winner); // winner stays
+                say!(self.quiet, "bye-bye {loser}");
+                self.knocked.0.push(loser); // loser get's eliminated: knocked out
+                self.finish_round();
+            }
+            Stage::Idle | Stage::Done => {}
         }
+        Ok(())
+    }
 
-        // uneven number of players: we need a special pre-match-duel
-        if next_winner_b.0.len() != 1 && next_winner_b.0.len() % 2 == 1 {
-            print!("\nspecial winner duel: ");
-            let loser = Duel::handle_special(&mut next_winner_b);
-            next_loser_b.0.push(loser); // loser get's pushed to loser branch
-        }
+    fn byes(&self) -> Vec<Player> {
+        byes(self.duels.as_ref())
+    }
 
-        // uneven number of players: we need a special pre-match-duel
-        if next_loser_b.0.len() != 1 && next_loser_b.0.len() % 2 == 1 {
-            println!("\nspecial loser duel: ");
-            let loser = Duel::handle_special(&mut next_loser_b);
-            println!("bye-bye {loser}");
-            self.knocked.0.push(loser); // loser get's eliminated: knocked out
-        }
-        // and we apply the changes by turning new branches into duels
-        self.winner_branch = next_winner_b;
-        self.loser_branch = next_loser_b;
+    fn branches(&self) -> &'static [(Branch, &'static str)] {
+        &[
+            (Branch::Winner, "Winner branch"),
+            (Branch::Loser, "Loser branch"),
+            (Branch::Final, "Grand final"),
+        ]
     }
 
-    fn print_status(&self) {
-        // winner branch duels this round
-        println!("--------\n\nWinner branch players:\n");
-        for w_player in &self.winner_branch.0 {
-            println!("    {w_player}");
-        }
-        // loser branch duels this round
-        println!("\n-----------------------------\n\nLosing branch players:\n");
-        for l_player in &self.loser_branch.0 {
-            println!("    {l_player}");
+    fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+    fn standings(&self) -> Vec<Standing> {
+        // players of the actual stage are in the branch the stage belongs to
+        let playing = players_of(self.duels.as_ref());
+        let playing_in_winner = matches!(
+            self.stage,
+            Stage::Winner | Stage::SpecialWinner | Stage::Final
+        );
+        let (mut winners, mut losers) = if playing_in_winner {
+            (playing, vec![])
+        } else {
+            (vec![], playing)
+        };
+        winners.extend(self.winner_branch.0.iter().cloned());
+        losers.extend(self.loser_branch.0.iter().cloned());
+        losers.extend(self.new_losers.0.iter().cloned());
+
+        let still_in = [
+            (winners, "winner branch".into()),
+            (losers, "loser branch".into()),
+        ];
+        standings(still_in.into_iter().chain(knocked_groups(&self.knocked)))
+    }
+
+    fn print_status(&self, matches: &[Duel]) {
+        if matches.is_empty() {
+            println!("--------\n\nWinner branch players:\n");
+            for w_player in &self.winner_branch.0 {
+                println!("    {w_player}");
+            }
+        } else {
+            println!("--------\n");
+            bracket::print(matches, self.branches());
         }
         println!("\n-----------------------------\n\n");
     }
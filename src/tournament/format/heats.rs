@@ -0,0 +1,219 @@
+use super::*;
+use crate::tournament::heat::Heat;
+
+#[derive(Default, PartialEq, Eq, Clone, Debug)]
+/// heats of players in rounds: the best `advance` of every heat of at most `size` players go on to the next round,
+/// till all of them fit into a single one: the final
+///
+/// a heat is played as the [`Duel`]s of every pair of it's players, sharing it's id, see [`Heat::duels`],
+/// it's ranking is by the number of them won by it's players
+pub struct Heats {
+    /// the most players in a heat
+    pub size: usize,
+    /// the number of players going on from a heat
+    pub advance: usize,
+    /// the ones still in, the better ones first
+    players: Vec<Player>,
+    /// late entrants, playing from the next round on
+    entering: Vec<Player>,
+    /// every heat run so far, round by round
+    rounds: Vec<Vec<Heat>>,
+    /// heats of the actual round
+    heats: Option<Vec<Heat>>,
+    /// the round's [`Duel`]s have been applied
+    applied: bool,
+}
+
+impl Heats {
+    /// heats of at most `size` players, the best `advance` of them going on
+    ///
+    /// # Errors
+    ///
+    /// if `advance` isn't less than `size`
+    pub fn new(size: usize, advance: usize) -> Result<Self> {
+        if advance == 0 || advance >= size {
+            return Err(TournamentError::InvalidInput(
+                "fewer players have to advance from a heat than it's size",
+            ));
+        }
+        Ok(Self {
+            size,
+            advance,
+            ..Default::default()
+        })
+    }
+    /// the heats of the next round: the fewest ones the players fit in, of about the same size
+    ///
+    /// players are dealt to them one by one in their order, back and forth, so that the better ones,
+    /// the winners of the previous round, are spread evenly, if `shuffle`, they're shuffled before
+    fn next_heats(&mut self, shuffle: bool) -> Vec<Heat> {
+        let mut players = self.players.clone();
+        players.append(&mut self.entering);
+        if shuffle {
+            fastrand::shuffle(&mut players);
+        }
+        let count = players.len().div_ceil(self.size);
+        let mut heats = vec![Vec::new(); count];
+        for (i, player) in players.into_iter().enumerate() {
+            let (tier, idx) = (i / count, i % count);
+            let idx = if tier % 2 == 0 { idx } else { count - 1 - idx };
+            heats[idx].push(player);
+        }
+        let round = self.rounds.len() + 1;
+        heats
+            .into_iter()
+            .enumerate()
+            .map(|(i, players)| Heat::new(MatchId::new(Branch::Round, round, i + 1), players))
+            .collect()
+    }
+    /// the number of players going on from `heat`: at least one is knocked out, unless it's alone
+    fn advancing(&self, heat: &Heat) -> usize {
+        self.advance
+            .min(heat.players.len().saturating_sub(1))
+            .max(1)
+    }
+    /// every heat run so far, round by round
+    pub fn rounds(&self) -> &[Vec<Heat>] {
+        &self.rounds
+    }
+}
+
+impl Format for Heats {
+    fn add_players(&mut self, players: Players) {
+        self.players = players.0;
+    }
+    /// the best ones are dealt first, so they're spread over the heats
+    fn add_seeded_players(&mut self, players: Players) {
+        self.add_players(players);
+    }
+    fn initial_shuffle(&mut self) {
+        fastrand::shuffle(&mut self.players);
+    }
+
+    fn is_end(&self) -> bool {
+        self.heats.is_none() && self.rounds.last().is_some_and(|heats| heats.len() == 1)
+    }
+
+    fn next_duels(&mut self, shuffle: bool) -> Option<Vec<Duel>> {
+        if self.applied {
+            self.applied = false;
+            return None;
+        }
+        if self.heats.is_none() {
+            self.heats = Some(self.next_heats(shuffle));
+        }
+        let heats = self.heats.as_ref()?;
+        Some(heats.iter().flat_map(Heat::duels).collect())
+    }
+
+    fn apply(&mut self, played: Vec<Duel>) -> Result<()> {
+        let Some(heats) = &self.heats else {
+            return Ok(());
+        };
+        let mut heats = heats.clone();
+        for heat in &mut heats {
+            // every duel of the heat, as it's been played
+            let duels = heat
+                .duels()
+                .into_iter()
+                .map(|duel| {
+                    played
+                        .iter()
+                        .find(|p| (p.id, &p.homie, &p.guest) == (duel.id, &duel.homie, &duel.guest))
+                        .filter(|p| p.outcome.is_some())
+                        .cloned()
+                        .ok_or(TournamentError::NoOutcome(heat.id))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            heat.rank_by_wins(&duels);
+        }
+        // the winners first, then the second ones...
+        let advancing = heats
+            .iter()
+            .map(|heat| &heat.ranking[..self.advancing(heat)])
+            .collect::<Vec<_>>();
+        let most = advancing.iter().map(|players| players.len()).max();
+        self.players = (0..most.unwrap_or_default())
+            .flat_map(|place| {
+                advancing
+                    .iter()
+                    .filter_map(move |players| players.get(place))
+            })
+            .cloned()
+            .collect();
+        self.rounds.push(heats);
+        self.heats = None;
+        self.applied = true;
+        Ok(())
+    }
+
+    fn enter_late(&mut self, player: Player) -> Result<Vec<Duel>> {
+        if self.heats.as_ref().is_some_and(|heats| heats.len() == 1) {
+            return Err(TournamentError::LateEntry("the final is already running"));
+        }
+        self.entering.push(player);
+        Ok(Vec::new())
+    }
+
+    fn heats(&self) -> Vec<Heat> {
+        self.heats.clone().unwrap_or_default()
+    }
+
+    /// the ones still in share the first place,
+    /// players knocked out in the same round share their place with the ones of the same place in their heats
+    fn standings(&self) -> Vec<Standing> {
+        let mut groups = Vec::new();
+        if !self.is_end() {
+            let still_in = [self.players.as_slice(), &self.entering].concat();
+            groups.push((still_in, "still in".to_string()));
+        }
+        for (round, heats) in self.rounds.iter().enumerate().rev() {
+            if self.is_end() && round + 1 == self.rounds.len() {
+                let groups_of_one = heats[0].ranking.iter().map(|player| {
+                    let note = format!("final, {}.", heats[0].place_of(player).unwrap_or_default());
+                    (vec![player.clone()], note)
+                });
+                groups.extend(groups_of_one);
+                continue;
+            }
+            let longest = heats.iter().map(|heat| heat.ranking.len()).max();
+            for place in 0..longest.unwrap_or_default() {
+                let knocked = heats
+                    .iter()
+                    .filter(|heat| place >= self.advancing(heat))
+                    .filter_map(|heat| heat.ranking.get(place).cloned())
+                    .collect::<Vec<_>>();
+                if !knocked.is_empty() {
+                    let note = format!("round {}, {}. in heat", round + 1, place + 1);
+                    groups.push((knocked, note));
+                }
+            }
+        }
+        standings(groups)
+    }
+
+    fn print_status(&self, _: &[Duel]) {
+        println!("\n\nHEATS run so far:\n");
+        for heat in self.rounds.iter().flatten() {
+            println!("    {heat}");
+        }
+        println!("\n\nSTANDINGS after round {}:\n", self.rounds.len());
+        for standing in self.standings() {
+            println!(
+                "    {}. {}: {}",
+                standing.place, standing.player, standing.note
+            );
+        }
+        println!("\n\n\n");
+    }
+
+    fn results(self) -> Players {
+        let mut results = self
+            .standings()
+            .into_iter()
+            .map(|standing| standing.player)
+            .collect::<Vec<_>>();
+        results.reverse();
+        Players(results)
+    }
+}
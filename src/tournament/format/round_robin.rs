@@ -10,6 +10,13 @@ pub struct RoundRobin {
     pub points: HashMap<Player, u8>,
     /// the number of `round`s already executed
     pub round: usize,
+    /// [`Duel`]s of the actual round, byes included
+    duels: Option<Vec<Duel>>,
+    /// the round's [`Duel`]s have been applied
+    applied: bool,
+    /// the [`Duel`]s still to play, once the rounds to come are re-paired for a late entrant,
+    /// see [`Format::enter_late`]
+    remaining: Option<Vec<Duel>>,
 }
 
 impl RoundRobin {
@@ -17,12 +24,51 @@ impl RoundRobin {
     pub fn len(&self) -> usize {
         self.players.0.len()
     }
+    /// there are no [`Self::players`]
+    pub fn is_empty(&self) -> bool {
+        self.players.0.is_empty()
+    }
     /// update the [`Self::duels`], so in the upcoming round [`Player`]s play against other ones as well
     /// circle-method, implemented according to wikipedia <https://en.wikipedia.org/wiki/Round-robin_tournament#Circle_method>
+    ///
+    /// once re-paired for a late entrant, they're taken from the [`Self::remaining`] ones instead
     pub fn gen_duels(&mut self) -> Vec<Duel> {
+        match &self.remaining {
+            Some(remaining) => self.pair_remaining(remaining),
+            None => self.duels_of(self.round),
+        }
+    }
+    /// a round of the `remaining` [`Duel`]s, byes included: the ones of players with the most of them left
+    /// come first, so that the tournament ends as soon as possible
+    fn pair_remaining(&self, remaining: &[Duel]) -> Vec<Duel> {
+        let left = |player: &Player| remaining.iter().filter(|duel| duel.has(player)).count();
+        let mut candidates = remaining.to_vec();
+        candidates.sort_by_key(|duel| std::cmp::Reverse(left(&duel.homie) + left(&duel.guest)));
+        let mut duels: Vec<Duel> = Vec::new();
+        for duel in candidates {
+            if !duels
+                .iter()
+                .any(|paired| paired.has(&duel.homie) || paired.has(&duel.guest))
+            {
+                duels.push(duel);
+            }
+        }
+        // the ones left out have a bye
+        let byes = self
+            .players
+            .0
+            .iter()
+            .filter(|player| !player.is_unset() && !duels.iter().any(|duel| duel.has(player)))
+            .map(|player| Duel::new(player.clone(), Player::default()))
+            .collect::<Vec<_>>();
+        duels.extend(byes);
+        duels
+    }
+    /// the [`Duel`]s of `round`, byes included
+    pub(super) fn duels_of(&self, round: usize) -> Vec<Duel> {
         // the indexed order of duels
         let mut duel_idxs = (1..self.len()).collect::<Vec<_>>();
-        duel_idxs.rotate_right(self.round);
+        duel_idxs.rotate_right(round);
         duel_idxs.insert(0, 0);
 
         let mut duels = vec![];
@@ -43,6 +89,7 @@ impl RoundRobin {
             players,
             points,
             round,
+            ..Default::default()
         }
     }
 }
@@ -63,46 +110,161 @@ impl Format for RoundRobin {
 
     fn is_end(&self) -> bool {
         // every player played against every player
-        self.round == self.len() - 1
+        let played = match &self.remaining {
+            Some(remaining) => remaining.is_empty(),
+            None => self.round == self.len() - 1,
+        };
+        played && self.duels.is_none()
+    }
+
+    fn next_duels(&mut self, _: bool) -> Option<Vec<Duel>> {
+        if self.applied {
+            self.applied = false;
+            return None;
+        }
+        // NOTE: a round robin round always has playable duels
+        if self.duels.is_none() {
+            self.duels = Some(self.gen_duels());
+        }
+        let first = MatchId::new(Branch::Round, self.round + 1, 1);
+        self.duels.as_mut().map(|duels| pending(duels, first))
     }
 
-    fn play_round(&mut self, _: bool) {
+    /// with an odd number of players, `player` takes over the slot of the bye: it's opponents in the rounds
+    /// already started have won against `player` by walkover
+    ///
+    /// otherwise the rounds to come are re-paired, so that `player` plays against everyone,
+    /// a ghost is added if needed: bye
+    ///
+    /// # Errors
+    ///
+    /// if the tournament has ended
+    fn enter_late(&mut self, player: Player) -> Result<Vec<Duel>> {
+        if self.is_end() {
+            return Err(TournamentError::LateEntry("the tournament has ended"));
+        }
+        let ghost = Player::default();
+        // the actual round is missed as well, if it's already started
+        let started = self.round + usize::from(self.duels.is_some());
+        let bye = self.players.0.iter().position(|p| *p == ghost);
+        if let Some(idx) = bye.filter(|_| self.remaining.is_none()) {
+            let walkovers = (0..started)
+                .filter_map(|round| {
+                    let duels = self.duels_of(round);
+                    // after the playable ones of the round
+                    let nr = duels.iter().filter(|duel| !is_bye(duel)).count() + 1;
+                    let opponent = duels.into_iter().find_map(|duel| {
+                        match (duel.homie == ghost, duel.guest == ghost) {
+                            (true, _) => Some(duel.guest),
+                            (_, true) => Some(duel.homie),
+                            _ => None,
+                        }
+                    })?;
+                    Some(Duel {
+                        id: MatchId::new(Branch::Round, round + 1, nr),
+                        ..Duel::new(opponent, player.clone()).with_outcome(Some(true))
+                    })
+                })
+                .collect::<Vec<_>>();
+            for walkover in &walkovers {
+                self.points
+                    .entry(walkover.homie.clone())
+                    .and_modify(|p| *p += 1);
+            }
+            self.points.remove(&ghost);
+            self.points.insert(player.clone(), 0);
+            self.players.0[idx] = player;
+            return Ok(walkovers);
+        }
+        let mut remaining = match self.remaining.take() {
+            Some(remaining) => remaining,
+            None => {
+                let played = (0..started)
+                    .flat_map(|round| self.duels_of(round))
+                    .collect::<Vec<_>>();
+                let real = self.players.0.iter().filter(|p| !p.is_unset());
+                real.clone()
+                    .enumerate()
+                    .flat_map(|(i, homie)| {
+                        real.clone().skip(i + 1).map(move |guest| (homie, guest))
+                    })
+                    .filter(|(homie, guest)| !played.iter().any(|d| d.has(homie) && d.has(guest)))
+                    .map(|(homie, guest)| Duel::new(homie.clone(), guest.clone()))
+                    .collect()
+            }
+        };
+        self.players.0.retain(|p| *p != ghost);
+        self.points.remove(&ghost);
+        remaining.extend(
+            self.players
+                .0
+                .iter()
+                .map(|opponent| Duel::new(opponent.clone(), player.clone())),
+        );
+        self.points.insert(player.clone(), 0);
+        self.players.0.push(player);
+        if self.len() % 2 == 1 {
+            self.players.0.push(ghost.clone());
+            self.points.insert(ghost, 0);
+        }
+        self.remaining = Some(remaining);
+        Ok(Vec::new())
+    }
+
+    fn apply(&mut self, played: Vec<Duel>) -> Result<()> {
+        let Some(duels) = &mut self.duels else {
+            return Ok(());
+        };
+        settle(duels, played)?;
+        let duels = self.duels.take().unwrap_or_default();
+        if let Some(remaining) = &mut self.remaining {
+            remaining.retain(|left| {
+                !duels
+                    .iter()
+                    .any(|duel| duel.has(&left.homie) && duel.has(&left.guest))
+            });
+        }
         // execute duels: get outcomes
-        for duel in self.gen_duels() {
-            println!("\n{duel}");
+        for duel in duels {
             // ignore duel if any players are ghosts
-            if duel.homie.is_unset() || duel.guest.is_unset() {
+            if is_bye(&duel) {
                 continue;
             }
-            // execute duel
-            let (winner, _loser) = duel.clone().play();
+            let (winner, _loser) = duel.into_result()?;
             // winner get's a point
             self.points.entry(winner).and_modify(|p| *p += 1);
         }
         // another round is executed
         self.round += 1;
+        self.applied = true;
+        Ok(())
     }
 
-    fn print_status(&self) {
-        println!("\n\nPOINTS:\n");
-        for player in &self.players.0 {
-            println!("    {player}: {}", self.points[player]);
-        }
-        println!("\n\n\n");
+    fn standings(&self) -> Vec<Standing> {
+        let mut players = self
+            .players
+            .0
+            .iter()
+            .filter(|player| !player.is_unset())
+            .map(|player| (player.clone(), self.points[player]))
+            .collect::<Vec<_>>();
+        // stable: original order on equal points
+        players.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+        by_points(players)
+    }
+
+    fn print_status(&self, matches: &[Duel]) {
+        let table = Crosstable::from_standings(&self.standings(), matches);
+        println!("\n\nCROSSTABLE:\n\n{}\n\n\n", table.render());
     }
 
     fn results(self) -> Players {
-        // hashmap -> vec
-        let mut result: Vec<_> = self.points.into_iter().collect();
-        // don't include bye in results
-        if result.last().is_some_and(|p| p.0.is_unset()) {
-            result.pop();
-        }
-        // sorted by points
-        result.sort_by(|x, y| x.1.cmp(&y.1));
-        // extract players
-        let result: Vec<_> = result.into_iter().map(|(player, _points)| player).collect();
-        // into Players
-        Players(result)
+        let mut results = self
+            .standings()
+            .into_iter()
+            .map(|standing| standing.player)
+            .collect::<Vec<_>>();
+        results.reverse();
+        Players(results)
     }
 }
@@ -5,11 +5,63 @@ use super::*;
 pub struct SingleElimination {
     pub branch: Players,
     pub knocked: Players,
+    /// the number of the actual round, starting from 1
+    pub round: usize,
+    /// the number of the last [`Duel`] of the actual round: the special one's numbered on from it
+    numbered: usize,
+    /// stage of the actual round
+    stage: Stage,
+    /// [`Duel`]s of the actual stage, byes included
+    duels: Option<Vec<Duel>>,
+    /// doesn't print what's happening, see [`Format::set_quiet`]
+    quiet: bool,
+}
+
+/// stages of a round of [`SingleElimination`]
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+enum Stage {
+    /// the round hasn't started yet
+    #[default]
+    Idle,
+    /// duels of the branch
+    Branch,
+    /// the losers of the semi-finals play for the third place
+    ThirdPlace,
+    /// number of players in the branch isn't divisible by 2: special pre-match duel
+    Special,
+    /// the round is over
+    Done,
 }
 
 impl SingleElimination {
     pub fn new(branch: Players, knocked: Players) -> Self {
-        Self { branch, knocked }
+        Self {
+            branch,
+            knocked,
+            ..Default::default()
+        }
+    }
+    /// prepare the [`Duel`]s of the actual stage
+    ///
+    /// `false` if the round is over
+    fn prepare(&mut self, shuffle: bool) -> bool {
+        match self.stage {
+            Stage::Idle => {
+                say!(self.quiet, "\nduels:");
+                let branch = std::mem::take(&mut self.branch);
+                self.duels = Some(branch.into_duels(shuffle));
+                self.stage = Stage::Branch;
+                self.round += 1;
+                true
+            }
+            Stage::Done => {
+                self.stage = Stage::Idle;
+                self.numbered = 0;
+                false
+            }
+            // those are prepared when the previous stage is applied
+            Stage::Branch | Stage::ThirdPlace | Stage::Special => true,
+        }
     }
 }
 
@@ -22,60 +74,134 @@ impl Format for SingleElimination {
     }
 
     fn is_end(&self) -> bool {
-        self.branch.0.is_empty()
+        self.branch.0.is_empty() && self.duels.is_none()
+    }
+
+    fn next_duels(&mut self, shuffle: bool) -> Option<Vec<Duel>> {
+        loop {
+            if self.duels.is_none() && !self.prepare(shuffle) {
+                return None;
+            }
+            // the special duel is a part of the round it's played in
+            let first = match self.stage {
+                Stage::ThirdPlace => MatchId::new(Branch::ThirdPlace, 1, 1),
+                _ => MatchId::new(Branch::Round, self.round, self.numbered + 1),
+            };
+            let pending = pending(self.duels.as_mut()?, first);
+            if let Some(last) = pending.last() {
+                if self.stage == Stage::Branch {
+                    self.numbered = last.id.nr;
+                }
+                return Some(pending);
+            }
+            self.apply(Vec::new()).ok()?;
+        }
     }
 
-    fn play_round(&mut self, standard: bool) {
-        // winner branch of the next round
-        let mut next_branch = Players::default();
-        // knocked players of the next round
-        let knocked = &mut self.knocked;
+    fn apply(&mut self, played: Vec<Duel>) -> Result<()> {
+        let Some(duels) = &mut self.duels else {
+            return Ok(());
+        };
+        settle(duels, played)?;
+        let mut duels = self.duels.take().unwrap_or_default();
 
-        let branch = std::mem::take(&mut self.branch);
-        let mut branch_d = branch.into_duels(!standard);
+        match self.stage {
+            Stage::Branch => {
+                // get outcomes for branch duels, move contestants to other branch if necessary
+                while let Some(duel) = duels.pop() {
+                    // duel isn't ready yet to be played, waiting for opponent
+                    if duel.guest.is_unset() {
+                        self.branch.0.push(duel.homie); // should get into the next-round winner branch
+                        continue;
+                    }
+                    let (winner, loser) = duel.into_result()?;
+                    self.branch.0.push(winner); // winner get's to winner branch
+                    say!(self.quiet, "bye-bye {loser}");
+                    self.knocked.0.push(loser); // loser get's to loser branch
+                }
+                say!(self.quiet, "\n-----------------------------");
 
-        // get outcomes for branch duels, move contestants to other branch if necessary
-        while let Some(duel) = branch_d.pop() {
-            // duel isn't ready yet to be played, waiting for opponent
-            if duel.guest.is_unset() {
-                next_branch.0.push(duel.homie); // should get into the next-round winner branch
-                continue;
+                // handle special cases on winner branch
+                if self.branch.0.len() == 1 {
+                    self.knocked.0.push(self.branch.0.pop().unwrap());
+                    self.stage = Stage::Done;
+                } else if self.branch.0.len() == 2 {
+                    // the semi-final losers: with 3 players there's only one of them
+                    match (self.knocked.0.pop(), self.knocked.0.pop()) {
+                        (Some(homie), Some(guest)) => {
+                            say!(self.quiet, "Third place duel:");
+                            self.duels = Some(vec![Duel::new(homie, guest)]);
+                            self.stage = Stage::ThirdPlace;
+                        }
+                        (homie, _) => {
+                            self.knocked.0.extend(homie);
+                            self.stage = Stage::Done;
+                        }
+                    }
+                } else if self.branch.0.len() % 2 == 1 {
+                    // not divisible by 2: we need a special pre-match: duel
+                    say!(self.quiet, "\nspecial duel:");
+                    self.duels = Some(vec![Duel::special(&mut self.branch)]);
+                    self.stage = Stage::Special;
+                } else {
+                    self.stage = Stage::Done;
+                }
+            }
+            Stage::ThirdPlace => {
+                let (third, fourth) = duels.pop().unwrap().into_result()?;
+                self.knocked.0.push(fourth);
+                self.knocked.0.push(third);
+                self.stage = Stage::Done;
+            }
+            Stage::Special => {
+                let (winner, loser) = duels.pop().unwrap().into_result()?;
+                self.branch.0.push(winner); // winner stays
+                self.knocked.0.push(loser); // loser get's knocked out
+                self.stage = Stage::Done;
             }
-            println!("\nduel: {duel}");
-            // play the duel, that leads us to having the result
-            let (winner, loser) = duel.play();
-            next_branch.0.push(winner); // winner get's to winner branch
-            println!("bye-bye {loser}");
-            knocked.0.push(loser); // loser get's to loser branch
+            Stage::Idle | Stage::Done => {}
         }
-        println!("\n-----------------------------");
+        Ok(())
+    }
 
-        // handle special cases on winner branch
-        if next_branch.0.len() == 1 {
-            self.knocked.0.push(next_branch.0.pop().unwrap());
-        } else if next_branch.0.len() == 2 {
-            print!("Third place duel: ");
-            let mut tmp_branch = Players(vec![knocked.0.pop().unwrap(), knocked.0.pop().unwrap()]);
-            let loser = Duel::handle_special(&mut tmp_branch);
-            let (third, fourth) = (tmp_branch.0.pop().unwrap(), loser);
-            self.knocked.0.push(fourth);
-            self.knocked.0.push(third);
-        } else if next_branch.0.len() % 2 == 1 {
-            // not divisible by 2: we need a special pre-match: duel
-            print!("\nspecial duel: ");
-            let loser = Duel::handle_special(&mut next_branch);
-            knocked.0.push(loser); // loser get's knocked out
-        }
+    fn byes(&self) -> Vec<Player> {
+        byes(self.duels.as_ref())
+    }
 
-        // and we apply the changes by turning new branches into duels
-        self.branch = next_branch;
+    fn branches(&self) -> &'static [(Branch, &'static str)] {
+        &[
+            (Branch::Round, "Bracket"),
+            (Branch::ThirdPlace, "Third place"),
+        ]
     }
 
-    fn print_status(&self) {
-        // winner branch duels this round
-        println!("--------\n\nPlayers:\n");
-        for player in &self.branch.0 {
-            println!("    {player}");
+    fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+    fn standings(&self) -> Vec<Standing> {
+        let mut groups = Vec::new();
+        if self.stage == Stage::ThirdPlace {
+            // the finalists wait in the branch, the semi-final losers play for the third place
+            groups.push((self.branch.0.clone(), "still in".into()));
+            let third = players_of(self.duels.as_ref());
+            groups.push((third, "third place duel".into()));
+        } else {
+            let mut still_in = players_of(self.duels.as_ref());
+            still_in.extend(self.branch.0.iter().cloned());
+            groups.push((still_in, "still in".into()));
+        }
+        standings(groups.into_iter().chain(knocked_groups(&self.knocked)))
+    }
+
+    fn print_status(&self, matches: &[Duel]) {
+        if matches.is_empty() {
+            println!("--------\n\nPlayers:\n");
+            for player in &self.branch.0 {
+                println!("    {player}");
+            }
+        } else {
+            println!("--------\n");
+            bracket::print(matches, self.branches());
         }
         println!("\n-----------------------------\n\n");
     }
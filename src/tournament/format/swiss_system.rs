@@ -1,33 +1,267 @@
 use super::*;
+use std::collections::HashMap;
 
 #[derive(Default, PartialEq, Eq, Clone, Debug)]
 /// implemented according to wikipedia <https://en.wikipedia.org/wiki/Swiss-system_tournament>
-pub struct SwissSystem {}
+///
+/// [`Player`]s are ranked by their points, ties are broken by their order: their seed
+pub struct SwissSystem {
+    /// all the participating [`Players`], in the order of their seed
+    pub players: Players,
+    /// points of the `players`: one for a win or a bye
+    pub points: HashMap<Player, u8>,
+    /// the number of `round`s already executed
+    pub round: usize,
+    /// the number of rounds to be played: enough to have a single winner
+    pub rounds: usize,
+    /// every pair of [`Player`]s who've already met
+    met: Vec<(Player, Player)>,
+    /// [`Player`]s who've already got a bye
+    had_bye: Vec<Player>,
+    /// [`Duel`]s of the actual round, byes included
+    duels: Option<Vec<Duel>>,
+    /// the round's [`Duel`]s have been applied
+    applied: bool,
+}
 
 impl SwissSystem {
-    //     pub fn new(branch: Vec<Duel>, knocked: Players) -> Self {
-    //         Self { branch, knocked }
-    //     }
+    pub fn new(players: Players, points: HashMap<Player, u8>, round: usize) -> Self {
+        let rounds = rounds(players.0.len());
+        Self {
+            players,
+            points,
+            round,
+            rounds,
+            ..Default::default()
+        }
+    }
+    /// `a` and `b` have already met
+    fn have_met(&self, a: &Player, b: &Player) -> bool {
+        self.met
+            .iter()
+            .any(|(x, y)| (x == a && y == b) || (x == b && y == a))
+    }
+    /// the actual ranking: by points, then by seed
+    fn ranking(&self) -> Vec<Player> {
+        let mut ranking = self.players.0.clone();
+        // stable: seed order on equal points
+        ranking.sort_by_key(|player| std::cmp::Reverse(self.points[player]));
+        ranking
+    }
+    /// sum of the points of the opponents of `player`: the Buchholz score, a tie-break
+    fn buchholz(&self, player: &Player) -> usize {
+        self.met
+            .iter()
+            .filter_map(|(a, b)| match () {
+                _ if a == player => Some(b),
+                _ if b == player => Some(a),
+                _ => None,
+            })
+            .map(|opponent| self.points[opponent] as usize)
+            .sum()
+    }
+    /// [`Duel`]s of the next round, the bye last
+    ///
+    /// the lowest ranked [`Player`] who hasn't got a bye yet gets it, the others are paired
+    /// within their score group: the top half against the bottom half, avoiding rematches if possible
+    pub fn gen_duels(&self) -> Vec<Duel> {
+        let mut ranking = self.ranking();
+        let bye = if ranking.len() % 2 == 1 {
+            let idx = ranking
+                .iter()
+                .rposition(|player| !self.had_bye.contains(player))
+                .unwrap_or(ranking.len() - 1);
+            Some(ranking.remove(idx))
+        } else {
+            None
+        };
+
+        let pairs = self
+            .pair(&ranking, false, &mut { PAIRING_ATTEMPTS })
+            .or_else(|| self.pair(&ranking, true, &mut { usize::MAX }))
+            .unwrap_or_default();
+        // the first pair shall be the first match: `pending` numbers them from the back
+        let mut duels = pairs
+            .into_iter()
+            .rev()
+            .map(|(homie, guest)| Duel::new(homie, guest))
+            .collect::<Vec<_>>();
+        if let Some(bye) = bye {
+            duels.insert(0, Duel::new(bye, Player::default()));
+        }
+        duels
+    }
+    /// pair `ranking` the best way possible, `None` if that's impossible without rematches,
+    /// unless `rematches` are allowed, or if no pairing's been found in `attempts`
+    fn pair(
+        &self,
+        ranking: &[Player],
+        rematches: bool,
+        attempts: &mut usize,
+    ) -> Option<Vec<(Player, Player)>> {
+        let Some((first, rest)) = ranking.split_first() else {
+            return Some(Vec::new());
+        };
+        // members of the score group of `first`
+        let group = rest
+            .iter()
+            .take_while(|player| self.points[*player] == self.points[first])
+            .count();
+        // the preferred opponent: the one in the same place in the bottom half of the score group
+        let half = group.div_ceil(2);
+        let candidates = (half.saturating_sub(1)..group)
+            .chain(0..half.saturating_sub(1))
+            .chain(group..rest.len());
+
+        for idx in candidates {
+            let opponent = &rest[idx];
+            if !rematches && self.have_met(first, opponent) {
+                continue;
+            }
+            if *attempts == 0 {
+                return None;
+            }
+            *attempts -= 1;
+            let mut others = rest.to_vec();
+            others.remove(idx);
+            if let Some(mut pairs) = self.pair(&others, rematches, attempts) {
+                pairs.insert(0, (first.clone(), opponent.clone()));
+                return Some(pairs);
+            }
+        }
+        None
+    }
+}
+
+/// the search for a pairing without rematches is given up after this many tries:
+/// it could take forever if there's none
+const PAIRING_ATTEMPTS: usize = 10_000;
+
+/// number of rounds needed for a single winner among `players` players
+fn rounds(players: usize) -> usize {
+    (players.max(2) as f64).log2().ceil() as usize
 }
 
 impl Format for SwissSystem {
     fn add_players(&mut self, players: Players) {
-        todo!()
+        self.rounds = rounds(players.0.len());
+        // set every player's points to 0
+        self.points = players.0.iter().map(|p| (p.clone(), 0)).collect();
+        self.players = players;
+    }
+    fn add_seeded_players(&mut self, players: Players) {
+        // pairing is based on the seed order itself
+        self.add_players(players);
+    }
+    fn initial_shuffle(&mut self) {
+        fastrand::shuffle(&mut self.players.0);
     }
 
     fn is_end(&self) -> bool {
-        todo!()
+        self.round == self.rounds && self.duels.is_none()
+    }
+    /// `player` is ranked last with no points: the rounds missed count as byes worth nothing,
+    /// so `player` won't get another bye
+    ///
+    /// # Errors
+    ///
+    /// if there's no round left for `player` to play
+    fn enter_late(&mut self, player: Player) -> Result<Vec<Duel>> {
+        if self.round + usize::from(self.duels.is_some()) >= self.rounds {
+            return Err(TournamentError::LateEntry(
+                "no rounds are left to be played",
+            ));
+        }
+        self.points.insert(player.clone(), 0);
+        self.had_bye.push(player.clone());
+        self.players.0.push(player);
+        Ok(Vec::new())
+    }
+
+    fn next_duels(&mut self, _: bool) -> Option<Vec<Duel>> {
+        if self.applied {
+            self.applied = false;
+            return None;
+        }
+        if self.duels.is_none() {
+            self.duels = Some(self.gen_duels());
+        }
+        let first = MatchId::new(Branch::Round, self.round + 1, 1);
+        self.duels.as_mut().map(|duels| pending(duels, first))
     }
 
-    fn play_round(&mut self, _: bool) {
-        todo!()
+    fn apply(&mut self, played: Vec<Duel>) -> Result<()> {
+        let Some(duels) = &mut self.duels else {
+            return Ok(());
+        };
+        settle(duels, played)?;
+        let duels = self.duels.take().unwrap_or_default();
+        for duel in duels {
+            if is_bye(&duel) {
+                // a bye is worth a point
+                self.points
+                    .entry(duel.homie.clone())
+                    .and_modify(|p| *p += 1);
+                self.had_bye.push(duel.homie);
+                continue;
+            }
+            self.met.push((duel.homie.clone(), duel.guest.clone()));
+            let (winner, _loser) = duel.into_result()?;
+            self.points.entry(winner).and_modify(|p| *p += 1);
+        }
+        self.round += 1;
+        self.applied = true;
+        Ok(())
+    }
+
+    fn byes(&self) -> Vec<Player> {
+        byes(self.duels.as_ref())
+    }
+
+    fn standings(&self) -> Vec<Standing> {
+        let mut players = self
+            .ranking()
+            .into_iter()
+            .map(|player| {
+                let score = (self.points[&player], self.buchholz(&player));
+                (player, score)
+            })
+            .collect::<Vec<_>>();
+        // stable: seed order on equal scores
+        players.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+        let mut groups: Vec<(Vec<Player>, String)> = Vec::new();
+        for (player, (points, buchholz)) in players {
+            let note = format!("{points} points, {buchholz} buchholz");
+            match groups.last_mut() {
+                Some((group, last)) if *last == note => group.push(player),
+                _ => groups.push((vec![player], note)),
+            }
+        }
+        standings(groups)
     }
 
-    fn print_status(&self) {
-        todo!()
+    fn print_status(&self, _: &[Duel]) {
+        println!(
+            "\n\nSTANDINGS after round {} of {}:\n",
+            self.round, self.rounds
+        );
+        for standing in self.standings() {
+            println!(
+                "    {}. {}: {}",
+                standing.place, standing.player, standing.note
+            );
+        }
+        println!("\n\n\n");
     }
 
     fn results(self) -> Players {
-        todo!()
+        let mut results = self
+            .standings()
+            .into_iter()
+            .map(|standing| standing.player)
+            .collect::<Vec<_>>();
+        results.reverse();
+        Players(results)
     }
 }
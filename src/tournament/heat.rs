@@ -0,0 +1,152 @@
+use super::structs::{Duel, MatchId, Player};
+
+/// a match of any number of players, decided by the order they finish in
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Heat {
+    /// `R<round>-<nr>`, see [`MatchId`]
+    pub id: MatchId,
+    pub players: Vec<Player>,
+    /// `players` in the order they've finished, empty till it's run
+    pub ranking: Vec<Player>,
+}
+
+impl std::fmt::Display for Heat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let players = match self.is_settled() {
+            true => &self.ranking,
+            false => &self.players,
+        };
+        let players = players.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let separator = if self.is_settled() { " > " } else { " / " };
+        write!(f, "{}: {}", self.id, players.join(separator))
+    }
+}
+
+impl Heat {
+    pub fn new(id: MatchId, players: Vec<Player>) -> Self {
+        Self {
+            id,
+            players,
+            ranking: Vec::new(),
+        }
+    }
+    /// `self` has been run: it's got a ranking
+    pub fn is_settled(&self) -> bool {
+        !self.ranking.is_empty()
+    }
+    /// rank `self` by `input`: the numbers of the players in the order they've finished, eg: `3 1 2`,
+    /// or their names separated by commas, eg: `cecil, anna, bob`, the last one may be left out
+    ///
+    /// # Errors
+    ///
+    /// if a player isn't found, is ranked more than once, or more than one is left out
+    pub fn rank(&mut self, input: &str) -> std::result::Result<(), &'static str> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("no ranking given");
+        }
+        let numbers = input
+            .split_whitespace()
+            .map(str::parse::<usize>)
+            .collect::<std::result::Result<Vec<_>, _>>();
+        let mut ranking = match numbers {
+            Ok(numbers) => numbers
+                .into_iter()
+                .map(|nr| {
+                    nr.checked_sub(1)
+                        .and_then(|idx| self.players.get(idx))
+                        .cloned()
+                        .ok_or("no player of such number in the heat")
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+            Err(_) => input
+                .split(',')
+                .map(|name| self.find(name))
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+        };
+        if ranking
+            .iter()
+            .enumerate()
+            .any(|(i, player)| ranking[..i].contains(player))
+        {
+            return Err("a player is ranked more than once");
+        }
+        let missing = self
+            .players
+            .iter()
+            .filter(|player| !ranking.contains(player))
+            .cloned()
+            .collect::<Vec<_>>();
+        if missing.len() > 1 {
+            return Err("every player has to be ranked, but the last one");
+        }
+        ranking.extend(missing);
+        self.ranking = ranking;
+        Ok(())
+    }
+    /// the player of `self` that `input` fits, see [`Player::fits`]
+    fn find(&self, input: &str) -> std::result::Result<Player, &'static str> {
+        let input = input.trim();
+        let fitting = self
+            .players
+            .iter()
+            .filter(|player| player.fits(input))
+            .collect::<Vec<_>>();
+        match fitting[..] {
+            [player] => Ok(player.clone()),
+            [] => Err("no such player in the heat"),
+            // eg: Bob and Bobby: the one with exactly that name
+            _ => fitting
+                .iter()
+                .find(|player| player.name.eq_ignore_ascii_case(input))
+                .map(|player| (*player).clone())
+                .ok_or("ambiguous player, type more of the name"),
+        }
+    }
+    /// the place `player` finished `self` in, starting from 1
+    pub fn place_of(&self, player: &Player) -> Option<usize> {
+        self.ranking
+            .iter()
+            .position(|ranked| ranked == player)
+            .map(|idx| idx + 1)
+    }
+    /// the [`Duel`]s of every pair of the players of `self`, sharing it's id,
+    /// settled by the ranking if it's got one: every player beat the ones finishing after them
+    pub fn duels(&self) -> Vec<Duel> {
+        let mut duels = Vec::new();
+        for (i, homie) in self.players.iter().enumerate() {
+            for guest in &self.players[i + 1..] {
+                let outcome = self
+                    .place_of(homie)
+                    .zip(self.place_of(guest))
+                    .map(|(homie, guest)| homie < guest);
+                duels.push(Duel {
+                    id: self.id,
+                    ..Duel::new(homie.clone(), guest.clone()).with_outcome(outcome)
+                });
+            }
+        }
+        duels
+    }
+    /// rank `self` by the number of `duels` of it won by it's players, the ones of the same number in their order
+    pub fn rank_by_wins(&mut self, duels: &[Duel]) {
+        let wins = |player: &Player| {
+            duels
+                .iter()
+                .filter(|duel| duel.id == self.id)
+                .filter(|duel| match duel.outcome {
+                    Some(true) => &duel.homie == player,
+                    Some(false) => &duel.guest == player,
+                    None => false,
+                })
+                .count()
+        };
+        let mut ranking = self.players.clone();
+        // stable: the order of the players on the same number of wins
+        ranking.sort_by_key(|player| std::cmp::Reverse(wins(player)));
+        self.ranking = ranking;
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,49 @@
+use super::*;
+use crate::tournament::{players::tests::nu_p, Branch};
+
+#[test]
+fn rank() {
+    let players = vec![
+        nu_p("Anna", 9, 'A'),
+        nu_p("Bob", 9, 'B'),
+        nu_p("Bobby", 9, 'C'),
+    ];
+    let mut heat = Heat::new(MatchId::new(Branch::Round, 1, 1), players.clone());
+    assert_eq!(
+        Err("no player of such number in the heat"),
+        heat.rank("4 1")
+    );
+    assert_eq!(Err("a player is ranked more than once"), heat.rank("1 1"));
+    assert_eq!(
+        Err("every player has to be ranked, but the last one"),
+        heat.rank("2")
+    );
+    assert!(!heat.is_settled());
+
+    // the last one's left out
+    heat.rank("bob, anna").unwrap();
+    let expected = vec![players[1].clone(), players[0].clone(), players[2].clone()];
+    assert_eq!(expected, heat.ranking);
+    assert_eq!(Some(3), heat.place_of(&players[2]));
+    heat.rank("3 2 1").unwrap();
+    assert_eq!(players[2], heat.ranking[0]);
+    assert_eq!(
+        Err("ambiguous player, type more of the name"),
+        heat.rank("b, anna")
+    );
+
+    let duels = heat.duels();
+    assert_eq!(3, duels.len());
+    // in the order of the players, all of them lost by the better placed guest
+    assert!(duels
+        .iter()
+        .all(|duel| duel.outcome == Some(false) && duel.id == heat.id));
+    assert_eq!(
+        (&players[0], &players[2]),
+        (&duels[1].homie, &duels[1].guest)
+    );
+    let mut unranked = Heat::new(heat.id, players.clone());
+    assert!(unranked.duels().iter().all(|duel| duel.outcome.is_none()));
+    unranked.rank_by_wins(&duels);
+    assert_eq!(heat.ranking, unranked.ranking);
+}
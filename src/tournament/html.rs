@@ -0,0 +1,216 @@
+use super::{
+    class_cup::ClassCup,
+    crosstable::{Cell, Crosstable},
+    format::{Format, Standing},
+    structs::{Branch, Duel, Player},
+};
+use std::fmt::Write;
+
+/// stylesheet of the page: no external assets needed
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; background: #fafafa; color: #222; }
+h1 { margin-top: 0; }
+.bracket { display: flex; gap: 2em; overflow-x: auto; padding-bottom: 1em; }
+.round { display: flex; flex-direction: column; min-width: 12em; }
+.round h3 { text-align: center; margin: 0 0 .5em; }
+.matches { display: flex; flex-direction: column; justify-content: space-around; flex-grow: 1; gap: 1em; }
+.match { border: 1px solid #999; border-radius: 4px; background: #fff; }
+.match .id { font-size: .75em; color: #666; padding: 2px 6px; border-bottom: 1px solid #ddd; }
+.match .player { padding: 2px 6px; }
+.match .won { font-weight: bold; background: #dfd; }
+.match .bye { color: #999; font-style: italic; }
+.match.pending { border-color: #e90; }
+table { border-collapse: collapse; background: #fff; }
+td, th { border: 1px solid #999; padding: 4px 8px; text-align: center; }
+td.name { text-align: left; }
+td.won { background: #dfd; }
+td.lost { background: #fdd; }
+td.itself { background: #ccc; }
+";
+
+/// escape `s` to be put into html or svg
+pub fn escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '&' => "&amp;".into(),
+            '<' => "&lt;".into(),
+            '>' => "&gt;".into(),
+            '"' => "&quot;".into(),
+            '\'' => "&#39;".into(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// a self-contained html page of the actual state of `format`:
+/// it's brackets or crosstable and standings, and the class cup if there's one
+///
+/// `matches`: every [`Duel`] so far: played, pending ones and byes
+pub fn render(format: &impl Format, matches: &[Duel], class_cup: Option<&ClassCup>) -> String {
+    let standings = format.standings();
+    let mut body = String::new();
+
+    let branches = format.branches();
+    if branches.is_empty() {
+        body += &crosstable(&Crosstable::from_standings(&standings, matches));
+    } else {
+        for (branch, title) in branches {
+            let bracket = bracket(matches, *branch);
+            if !bracket.is_empty() {
+                let _ = write!(body, "<h2>{}</h2>\n{bracket}", escape(title));
+            }
+        }
+    }
+    body += &standings_table(&standings);
+    if let Some(cup) = class_cup {
+        body += &class_cup_table(cup);
+    }
+
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<meta http-equiv=\"refresh\" content=\"30\">
+<title>Tuna Man</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>Tuna Man</h1>
+{body}</body>
+</html>
+"
+    )
+}
+
+/// the bracket of `branch` from `matches` as html, empty if it's got no [`Duel`]s
+fn bracket(matches: &[Duel], branch: Branch) -> String {
+    let mut matches = matches
+        .iter()
+        .filter(|duel| duel.id.branch == branch)
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        return String::new();
+    }
+    // byes: number 0, so they get to the end of their round
+    matches.sort_by_key(|duel| (duel.id.round, duel.id.nr == 0, duel.id.nr));
+
+    let mut html = String::from("<div class=\"bracket\">\n");
+    for round in matches.chunk_by(|a, b| a.id.round == b.id.round) {
+        let title = round[0].id.to_string();
+        let title = title.split('-').next().unwrap_or_default();
+        let _ = writeln!(
+            html,
+            "<div class=\"round\"><h3>{}</h3><div class=\"matches\">",
+            escape(title)
+        );
+        for duel in round {
+            html += &duel_box(duel);
+        }
+        html += "</div></div>\n";
+    }
+    html += "</div>\n";
+    html
+}
+
+/// a [`Duel`] as html
+fn duel_box(duel: &Duel) -> String {
+    let bye = duel.guest.is_unset();
+    let player = |player: &Player, won: bool| {
+        let class = if won { "player won" } else { "player" };
+        format!(
+            "<div class=\"{class}\">{}</div>",
+            escape(&player.to_string())
+        )
+    };
+    let (id, guest) = if bye {
+        (
+            String::new(),
+            "<div class=\"player bye\">bye</div>".to_string(),
+        )
+    } else {
+        (
+            format!("<div class=\"id\">{}</div>", duel.id),
+            player(&duel.guest, duel.outcome == Some(false)),
+        )
+    };
+    let class = if duel.outcome.is_none() {
+        "match pending"
+    } else {
+        "match"
+    };
+    format!(
+        "<div class=\"{class}\">{id}{}{guest}</div>\n",
+        player(&duel.homie, duel.outcome == Some(true))
+    )
+}
+
+/// `table` as html
+fn crosstable(table: &Crosstable) -> String {
+    let mut html = String::from("<h2>Crosstable</h2>\n<table>\n<tr><th></th><th>Player</th>");
+    for i in 1..=table.players.len() {
+        let _ = write!(html, "<th>{i}</th>");
+    }
+    html += "<th>Wins</th><th>Place</th></tr>\n";
+    for (i, player) in table.players.iter().enumerate() {
+        let _ = write!(
+            html,
+            "<tr><th>{}</th><td class=\"name\">{}</td>",
+            i + 1,
+            escape(&player.to_string())
+        );
+        for cell in &table.cells[i] {
+            let class = match cell {
+                Cell::Won => "won",
+                Cell::Lost => "lost",
+                Cell::Itself => "itself",
+                Cell::Pending | Cell::Empty => "",
+            };
+            let _ = write!(html, "<td class=\"{class}\">{}</td>", cell.symbol());
+        }
+        let _ = writeln!(
+            html,
+            "<td>{}</td><td>{}</td></tr>",
+            table.wins[i], table.places[i]
+        );
+    }
+    html += "</table>\n";
+    html
+}
+
+/// `standings` as html
+fn standings_table(standings: &[Standing]) -> String {
+    let mut html = String::from(
+        "<h2>Standings</h2>\n<table>\n<tr><th>Place</th><th>Player</th><th></th></tr>\n",
+    );
+    for standing in standings {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"name\">{}</td><td>{}</td></tr>",
+            standing.place,
+            escape(&standing.player.to_string()),
+            escape(&standing.note)
+        );
+    }
+    html += "</table>\n";
+    html
+}
+
+/// `cup` as html
+fn class_cup_table(cup: &ClassCup) -> String {
+    let mut html = String::from(
+        "<h2>Class cup</h2>\n<table>\n<tr><th>Place</th><th>Class</th><th>Points</th><th>Players</th></tr>\n",
+    );
+    for score in &cup.scores {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"name\">{}</td><td>{}</td><td>{}</td></tr>",
+            score.place, score.class, score.points, score.players
+        );
+    }
+    html += "</table>\n";
+    html
+}
+
+#[cfg(test)]
+pub mod tests;
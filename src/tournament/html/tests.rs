@@ -0,0 +1,6 @@
+use super::*;
+
+#[test]
+fn escaping() {
+    assert_eq!("Tom &amp; &lt;Jerry&gt;", escape("Tom & <Jerry>"));
+}
@@ -0,0 +1,521 @@
+use super::{Branch, Class, Duel, MatchId, Player, Players, Result, TournamentError};
+use serde::{Deserialize, Serialize};
+use std::{io::Write, path::Path};
+
+/// the commands understood at the ladder prompt
+pub const HELP: &str = "\
+commands:
+    <id> <winner> or <winner>               result of an open challenge, eg: `1 anna` or `anna`
+    challenge <challenger> / <challenged>   a new challenge, eg: `challenge bob / anna`
+    join <name>[, <class>]                  a new player at the bottom of the ladder, eg: `join Kovács Anna, 9A`
+    ladder                                  the ladder with the open challenges
+    history                                 every join, challenge and penalty so far
+    help                                    this help
+    quit                                    save and quit";
+
+/// what's been typed at the ladder prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// the result of an open challenge: `<id> <winner>` or `<winner>`, see [`Duel::find_outcome`]
+    Result(String),
+    /// the new challenge: `<challenger> / <challenged>`
+    Challenge(String),
+    /// the new player: `<name>[, <class>]`
+    Join(String),
+    Ladder,
+    History,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// the [`Command`] in `input`, a [`Command::Result`] if it's none of the others
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+        let (command, rest) = input.split_once(' ').unwrap_or((input, ""));
+        let rest = rest.trim().into();
+        match command.to_lowercase().as_str() {
+            "challenge" => Self::Challenge(rest),
+            "join" => Self::Join(rest),
+            "ladder" => Self::Ladder,
+            "history" => Self::History,
+            "help" | "?" => Self::Help,
+            "quit" | "q" => Self::Quit,
+            _ => Self::Result(input.into()),
+        }
+    }
+}
+
+/// a day: the number of days since 1970-01-01, written like `2024-09-30`
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "&str")]
+#[serde(into = "String")]
+pub struct Date(pub i64);
+
+impl Date {
+    /// the day of `year`, `month` and `day` of the month, both starting from 1
+    ///
+    /// implemented according to <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    pub fn new(year: i64, month: i64, day: i64) -> Self {
+        let year = if month <= 2 { year - 1 } else { year };
+        let (era, year_of_era) = (year.div_euclid(400), year.rem_euclid(400));
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Self(era * 146_097 + day_of_era - 719_468)
+    }
+    /// the actual day, in UTC
+    pub fn today() -> Self {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+        Self(now.map_or(0, |now| now.as_secs() / 86_400) as i64)
+    }
+    /// year, month and day of the month of `self`
+    ///
+    /// implemented according to <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    pub fn ymd(self) -> (i64, i64, i64) {
+        let days = self.0 + 719_468;
+        let (era, day_of_era) = (days.div_euclid(146_097), days.rem_euclid(146_097));
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+}
+impl TryFrom<&str> for Date {
+    type Error = &'static str;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let mut parts = value.trim().splitn(3, '-').map(str::parse::<i64>);
+        let (Some(Ok(year)), Some(Ok(month)), Some(Ok(day))) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err("invalid date, expected eg: 2024-09-30");
+        };
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err("invalid month or day of date");
+        }
+        Ok(Self::new(year, month, day))
+    }
+}
+impl From<Date> for String {
+    fn from(value: Date) -> Self {
+        let (year, month, day) = value.ymd();
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s: String = (*self).into();
+        write!(f, "{s}")
+    }
+}
+
+/// a place on the [`Ladder`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rung {
+    pub name: String,
+    pub class: Option<Class>,
+    /// the last day the player's joined, played or been penalized
+    pub last_active: Date,
+}
+impl Rung {
+    /// the [`Player`] on `self`
+    pub fn player(&self) -> Player {
+        Player {
+            name: self.name.clone(),
+            class: self.class,
+            nr: 0,
+        }
+    }
+}
+
+/// the kind of an [`Event`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Join,
+    Challenge,
+    /// the player's dropped down for being inactive
+    Penalty,
+}
+
+/// something that's happened on the [`Ladder`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub date: Date,
+    pub kind: Kind,
+    /// the one joining, challenging or penalized: `<name>[, <class>]`
+    pub player: String,
+    /// the one challenged
+    pub opponent: Option<String>,
+    /// the winner of the challenge, none till it's played
+    pub winner: Option<String>,
+}
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            date,
+            player,
+            opponent,
+            winner,
+            ..
+        } = self;
+        let opponent = opponent.as_deref().unwrap_or_default();
+        match (self.kind, winner) {
+            (Kind::Join, _) => write!(f, "{date}: {player} joined"),
+            (Kind::Penalty, _) => write!(f, "{date}: {player} dropped down for being inactive"),
+            (Kind::Challenge, None) => write!(f, "{date}: {player} challenged {opponent}"),
+            (Kind::Challenge, Some(winner)) => {
+                write!(f, "{date}: {player} challenged {opponent}, {winner} won")
+            }
+        }
+    }
+}
+
+/// the rules of the [`Ladder`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::Args)]
+pub struct Rules {
+    /// Players may challenge others at most this many places above them
+    #[arg(long, default_value_t = 3)]
+    pub reach: usize,
+    /// Players who haven't played for this many days drop down, unless they're in an open challenge
+    #[arg(long, default_value_t = 30)]
+    pub inactive_days: i64,
+    /// Number of places inactive players drop
+    #[arg(long, default_value_t = 1)]
+    pub penalty: usize,
+}
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            reach: 3,
+            inactive_days: 30,
+            penalty: 1,
+        }
+    }
+}
+
+/// an ongoing ranking: players challenge others above them, and swap places on a win
+///
+/// kept between sessions: the ladder in a csv file with `name,class,last_active` columns, from the top,
+/// and it's history in another one with `date,kind,player,opponent,winner` columns, oldest first
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ladder {
+    /// the first one's at the top
+    pub rungs: Vec<Rung>,
+    /// every [`Event`] so far, oldest first: the challenges without a winner are open
+    pub history: Vec<Event>,
+    pub rules: Rules,
+}
+
+impl Ladder {
+    /// load the ladder from the file at `path` and it's history from `history`, empty if they don't exist yet
+    pub fn load(path: impl AsRef<Path>, history: impl AsRef<Path>, rules: Rules) -> Result<Self> {
+        fn records<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
+            if !path.exists() {
+                return Ok(Vec::new());
+            }
+            let mut reader = csv::Reader::from_path(path)?;
+            Ok(reader.deserialize().collect::<csv::Result<_>>()?)
+        }
+        Ok(Self {
+            rungs: records(path.as_ref())?,
+            history: records(history.as_ref())?,
+            rules,
+        })
+    }
+    /// save the ladder to the file at `path` and it's history to `history`
+    pub fn save(&self, path: impl AsRef<Path>, history: impl AsRef<Path>) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)?;
+        self.rungs.iter().try_for_each(|r| writer.serialize(r))?;
+        writer.flush()?;
+        let mut writer = csv::Writer::from_path(history)?;
+        self.history.iter().try_for_each(|e| writer.serialize(e))?;
+        writer.flush()?;
+        Ok(())
+    }
+    /// the index of the rung of the [`Player`] `input` refers to, see [`Player::fits`]
+    fn find(&self, input: &str) -> Result<usize> {
+        let input = input.trim();
+        let fitting = (0..self.rungs.len())
+            .filter(|&idx| self.rungs[idx].player().fits(input))
+            .collect::<Vec<_>>();
+        // the one with exactly this name, if many fit
+        let exact = fitting
+            .iter()
+            .copied()
+            .filter(|&idx| self.rungs[idx].name.eq_ignore_ascii_case(input))
+            .collect::<Vec<_>>();
+        match (fitting.as_slice(), exact.as_slice()) {
+            ([], _) => Err(TournamentError::InvalidInput(
+                "no such player on the ladder",
+            )),
+            ([idx], _) | (_, [idx]) => Ok(*idx),
+            _ => Err(TournamentError::InvalidInput(
+                "ambiguous player, type more of the name",
+            )),
+        }
+    }
+    /// the place of `player` on the ladder, starting from 1
+    pub fn place_of(&self, player: &Player) -> Option<usize> {
+        let player = player.to_string();
+        self.rungs
+            .iter()
+            .position(|rung| rung.player().to_string() == player)
+            .map(|idx| idx + 1)
+    }
+    /// a new [`Player`] joins at the bottom `today`: `input` is `<name>[, <class>]`
+    ///
+    /// # Errors
+    ///
+    /// if `input` is invalid, or there's already a player of the same name and class
+    pub fn join(&mut self, input: &str, today: Date) -> Result<Player> {
+        let player = Player::try_from(input).map_err(TournamentError::InvalidInput)?;
+        if self.place_of(&player).is_some() {
+            return Err(TournamentError::InvalidInput("already on the ladder"));
+        }
+        self.rungs.push(Rung {
+            name: player.name.clone(),
+            class: player.class,
+            last_active: today,
+        });
+        self.history.push(Event {
+            date: today,
+            kind: Kind::Join,
+            player: player.to_string(),
+            opponent: None,
+            winner: None,
+        });
+        Ok(player)
+    }
+    /// indices of the open challenges in the history
+    fn open(&self) -> Vec<usize> {
+        (0..self.history.len())
+            .filter(|&idx| {
+                let event = &self.history[idx];
+                event.kind == Kind::Challenge && event.winner.is_none()
+            })
+            .collect()
+    }
+    /// the open challenges: the challenger's the homie, numbered from 1
+    pub fn challenges(&self) -> Vec<Duel> {
+        self.open()
+            .into_iter()
+            .enumerate()
+            .map(|(i, idx)| {
+                let event = &self.history[idx];
+                let player = |name: &str| Player::try_from(name).unwrap_or_default();
+                let opponent = event.opponent.as_deref().unwrap_or_default();
+                Duel {
+                    id: MatchId::new(Branch::Round, 1, i + 1),
+                    ..Duel::new(player(&event.player), player(opponent))
+                }
+            })
+            .collect()
+    }
+    /// a new challenge `today`: `input` is `<challenger> / <challenged>`, see [`Player::fits`]
+    ///
+    /// # Errors
+    ///
+    /// if any of them isn't found, or is in an open challenge already,
+    /// or the challenged isn't above the challenger within [`Rules::reach`]
+    pub fn challenge(&mut self, input: &str, today: Date) -> Result<(Player, Player)> {
+        let Some((challenger, challenged)) = input.split_once('/') else {
+            return Err(TournamentError::InvalidInput(
+                "expected <challenger> / <challenged>",
+            ));
+        };
+        let (challenger, challenged) = (self.find(challenger)?, self.find(challenged)?);
+        if challenged >= challenger || challenger - challenged > self.rules.reach {
+            return Err(TournamentError::InvalidInput(
+                "only players above within reach can be challenged",
+            ));
+        }
+        let (challenger, challenged) = (
+            self.rungs[challenger].player(),
+            self.rungs[challenged].player(),
+        );
+        if self
+            .challenges()
+            .iter()
+            .any(|duel| duel.has(&challenger) || duel.has(&challenged))
+        {
+            return Err(TournamentError::InvalidInput(
+                "already in an open challenge",
+            ));
+        }
+        self.history.push(Event {
+            date: today,
+            kind: Kind::Challenge,
+            player: challenger.to_string(),
+            opponent: Some(challenged.to_string()),
+            winner: None,
+        });
+        Ok((challenger, challenged))
+    }
+    /// the result of an open challenge played `today`: `input` is `<id> <winner>` or `<winner>`,
+    /// see [`Duel::find_outcome`]
+    ///
+    /// if the challenger's won, they swap places with the challenged, if they're still below them
+    ///
+    /// returns the challenge with it's outcome
+    pub fn result(&mut self, input: &str, today: Date) -> Result<Duel> {
+        let mut duels = self.challenges();
+        let (idx, outcome) = Duel::find_outcome(&duels, input)
+            .map_err(|e| TournamentError::InvalidInput(e.as_str()))?;
+        let mut duel = duels.swap_remove(idx);
+        duel.outcome = Some(outcome);
+        let winner = if outcome { &duel.homie } else { &duel.guest };
+        let event = self.open()[idx];
+        self.history[event].winner = Some(winner.to_string());
+
+        let (challenger, challenged) = (self.place_of(&duel.homie), self.place_of(&duel.guest));
+        for place in [challenger, challenged].into_iter().flatten() {
+            self.rungs[place - 1].last_active = today;
+        }
+        if let (true, Some(challenger), Some(challenged)) = (outcome, challenger, challenged) {
+            if challenger > challenged {
+                self.rungs.swap(challenger - 1, challenged - 1);
+            }
+        }
+        Ok(duel)
+    }
+    /// players inactive for [`Rules::inactive_days`] by `today` drop [`Rules::penalty`] places,
+    /// unless they're in an open challenge, they're active from `today` on
+    ///
+    /// returns the players penalized
+    pub fn penalize(&mut self, today: Date) -> Players {
+        let challenges = self.challenges();
+        let mut penalized = Vec::new();
+        // from the bottom: inactive players following each other keep their order
+        for idx in (0..self.rungs.len()).rev() {
+            let player = self.rungs[idx].player();
+            let inactive = today.0 - self.rungs[idx].last_active.0 >= self.rules.inactive_days;
+            if !inactive || challenges.iter().any(|duel| duel.has(&player)) {
+                continue;
+            }
+            let mut rung = self.rungs.remove(idx);
+            rung.last_active = today;
+            let to = (idx + self.rules.penalty).min(self.rungs.len());
+            self.rungs.insert(to, rung);
+            self.history.push(Event {
+                date: today,
+                kind: Kind::Penalty,
+                player: player.to_string(),
+                opponent: None,
+                winner: None,
+            });
+            penalized.push(player);
+        }
+        penalized.reverse();
+        Players(penalized)
+    }
+    /// the ladder with the open challenges
+    pub fn render(&self) -> String {
+        let mut lines = self
+            .rungs
+            .iter()
+            .enumerate()
+            .map(|(idx, rung)| {
+                let player = rung.player();
+                format!(
+                    "{:>4}. {player}, last active: {}",
+                    idx + 1,
+                    rung.last_active
+                )
+            })
+            .collect::<Vec<_>>();
+        let challenges = self.challenges();
+        if !challenges.is_empty() {
+            lines.push("\nopen challenges:".into());
+            lines.extend(
+                challenges
+                    .iter()
+                    .map(|duel| format!("    {}. {duel}", duel.id.nr)),
+            );
+        }
+        lines.join("\n")
+    }
+    /// execute what's been typed at the ladder prompt `today`, see [`Command`]
+    ///
+    /// returns `false` if the user's quit
+    pub fn prompt(&mut self, input: &str, today: Date) -> Result<bool> {
+        match Command::parse(input) {
+            Command::Result(input) => {
+                let duel = self.result(&input, today)?;
+                println!("{duel}");
+                let winner = if duel.outcome == Some(true) {
+                    &duel.homie
+                } else {
+                    &duel.guest
+                };
+                if let Some(place) = self.place_of(winner) {
+                    println!("{winner} is {place}. now");
+                }
+            }
+            Command::Challenge(input) => {
+                let (challenger, challenged) = self.challenge(&input, today)?;
+                println!("{challenger} challenged {challenged}");
+            }
+            Command::Join(input) => {
+                let player = self.join(&input, today)?;
+                println!("joined: {player}, {}.", self.rungs.len());
+            }
+            Command::Ladder => println!("\n{}\n", self.render()),
+            Command::History => {
+                for event in &self.history {
+                    println!("    {event}");
+                }
+            }
+            Command::Help => println!("{HELP}"),
+            Command::Quit => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// run a session of the ladder kept at `path` with it's history at `history`, by `rules`:
+/// the players of `join` join first, then inactive players are penalized, then commands are read from stdin,
+/// see [`Ladder::prompt`]
+///
+/// the ladder's saved after every command
+pub fn execute(path: &Path, history: &Path, join: Option<&Path>, rules: Rules) -> Result<()> {
+    let today = Date::today();
+    let mut ladder = Ladder::load(path, history, rules)?;
+    if let Some(join) = join {
+        for player in Players::load(join)?.0 {
+            if ladder.place_of(&player).is_none() {
+                ladder.join(&player.to_string(), today)?;
+            }
+        }
+    }
+    for player in ladder.penalize(today).0 {
+        println!("inactive, dropped down: {player}");
+    }
+    ladder.save(path, history)?;
+    println!("\nLADDER\n------\n\n{}\n\n{HELP}\n", ladder.render());
+    loop {
+        print!("ladder (<id> <winner>, 'help' for commands): ");
+        std::io::stdout().flush()?;
+        let mut buf = String::new();
+        std::io::stdin().read_line(&mut buf)?;
+        // nothing more to read
+        let input = if buf.is_empty() { "quit" } else { &buf };
+        let go_on = ladder.prompt(input, today).unwrap_or_else(|e| {
+            println!("{e}");
+            true
+        });
+        ladder.save(path, history)?;
+        if !go_on {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
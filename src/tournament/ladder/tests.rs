@@ -0,0 +1,96 @@
+use super::*;
+
+#[test]
+fn date() {
+    assert_eq!(Date(0), Date::new(1970, 1, 1));
+    assert_eq!((2024, 2, 29), Date::new(2024, 2, 29).ymd());
+    assert_eq!(Date::new(2024, 3, 1).0, Date::new(2024, 2, 29).0 + 1);
+    assert_eq!(Ok(Date::new(2000, 12, 31)), Date::try_from("2000-12-31"));
+    assert_eq!("1999-01-05", Date::new(1999, 1, 5).to_string());
+    assert!(Date::try_from("2000-13-01").is_err());
+    assert!(Date::try_from("yesterday").is_err());
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Command::Result("1 anna".into()), Command::parse("1 anna\n"));
+    assert_eq!(
+        Command::Challenge("bob / anna".into()),
+        Command::parse("challenge  bob / anna")
+    );
+    assert_eq!(
+        Command::Join("Bob, 9B".into()),
+        Command::parse("Join Bob, 9B")
+    );
+    assert_eq!(Command::Quit, Command::parse("q"));
+}
+
+#[test]
+fn ladder() {
+    let day = |day| Date::new(2024, 9, day);
+    let rules = Rules {
+        reach: 2,
+        inactive_days: 10,
+        penalty: 1,
+    };
+    let mut ladder = Ladder {
+        rules,
+        ..Default::default()
+    };
+    for name in ["Anna, 9A", "Bob, 9B", "Cecil", "Dora", "Emil"] {
+        ladder.join(name, day(1)).unwrap();
+    }
+    assert!(ladder.join("Bob, 9B", day(1)).is_err());
+    let names = |ladder: &Ladder| {
+        ladder
+            .rungs
+            .iter()
+            .map(|rung| rung.name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // out of reach, below, themselves
+    assert!(ladder.challenge("dora / anna", day(2)).is_err());
+    assert!(ladder.challenge("anna / bob", day(2)).is_err());
+    assert!(ladder.challenge("cecil / cecil", day(2)).is_err());
+    ladder.challenge("dora / bob", day(2)).unwrap();
+    assert!(
+        ladder.challenge("cecil / bob", day(2)).is_err(),
+        "already challenged"
+    );
+    ladder.challenge("emil / cecil", day(2)).unwrap();
+    assert_eq!(2, ladder.challenges().len());
+
+    // the challenger wins: swap
+    let duel = ladder.result("1 dora", day(3)).unwrap();
+    assert_eq!(Some(true), duel.outcome);
+    assert_eq!(vec!["Anna", "Dora", "Cecil", "Bob", "Emil"], names(&ladder));
+    // the challenged wins: no change
+    ladder.result("cecil", day(4)).unwrap();
+    assert_eq!(vec!["Anna", "Dora", "Cecil", "Bob", "Emil"], names(&ladder));
+    assert!(ladder.challenges().is_empty());
+    assert!(ladder.result("cecil", day(4)).is_err());
+
+    // Anna's been inactive since joining, Bob's in an open challenge
+    ladder.challenge("emil / bob", day(5)).unwrap();
+    let penalized = ladder.penalize(day(11));
+    assert_eq!(
+        Players(vec![Player::try_from("Anna, 9A").unwrap()]),
+        penalized
+    );
+    assert_eq!(vec!["Dora", "Anna", "Cecil", "Bob", "Emil"], names(&ladder));
+    assert!(ladder.penalize(day(12)).0.is_empty());
+    assert_eq!(
+        "2024-09-11: Anna, 9A dropped down for being inactive",
+        ladder.history.last().unwrap().to_string()
+    );
+
+    let dir = std::env::temp_dir();
+    let (path, history) = (
+        dir.join("tuna-man-ladder.csv"),
+        dir.join("tuna-man-ladder-history.csv"),
+    );
+    ladder.save(&path, &history).unwrap();
+    assert_eq!(ladder, Ladder::load(&path, &history, rules).unwrap());
+    assert_eq!(1, ladder.challenges().len(), "open challenges are kept");
+}
@@ -0,0 +1,211 @@
+use super::{
+    format::{self, Format, Supported},
+    Player, Players, Result, Tournament, TournamentError,
+};
+use std::path::Path;
+
+/// players advancing from every group into the knockout stage of a group+knockout tournament
+const ADVANCING: usize = 2;
+/// the fewest players in a group
+const MIN_GROUP: usize = 3;
+
+/// estimated schedule of a tournament
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    /// name of the format
+    pub name: String,
+    /// number of matches to be played
+    pub matches: usize,
+    /// number of rounds to be played
+    pub rounds: usize,
+    /// number of matches of every stage, in order: the ones of a stage may be played at the same time
+    pub stages: Vec<usize>,
+}
+
+impl Plan {
+    /// estimated duration in minutes with `tables` tables and matches lasting `match_duration` minutes
+    ///
+    /// a stage is started when the previous one is over, as the matches of a stage need the results of the previous
+    pub fn duration(&self, tables: usize, match_duration: usize) -> usize {
+        self.stages
+            .iter()
+            .map(|matches| matches.div_ceil(tables.max(1)) * match_duration)
+            .sum()
+    }
+}
+
+/// [`Plan`]s of every [`Supported`] format that can be played with `players` players
+/// and group+knockout splits of them
+pub fn plans(players: usize) -> Result<Vec<Plan>> {
+    use clap::ValueEnum;
+    let mut plans = Supported::value_variants()
+        .iter()
+        .map(|supported| {
+            supported.run(Planning {
+                name: supported.name(),
+                players,
+            })
+        })
+        // the ones that can't be played with this many players
+        .filter(|plan| !matches!(plan, Err(TournamentError::InvalidCount(_))))
+        .collect::<Result<Vec<_>>>()?;
+
+    // groups of round robin, the best ones of them in a single elimination
+    let mut groups = 2;
+    while players / groups >= MIN_GROUP {
+        plans.push(groups_and_knockout(players, groups)?);
+        groups *= 2;
+    }
+    Ok(plans)
+}
+
+/// the [`Plan`] of `groups` round robin groups played at the same time, then a single elimination of the best ones
+fn groups_and_knockout(players: usize, groups: usize) -> Result<Plan> {
+    // as even group sizes as possible
+    let group_plans = (0..groups)
+        .map(|group| {
+            let size = players / groups + usize::from(group < players % groups);
+            plan(String::new(), format::RoundRobin::default(), size)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let knockout = plan(
+        String::new(),
+        format::SingleElimination::default(),
+        groups * ADVANCING,
+    )?;
+
+    let group_stages = group_plans
+        .iter()
+        .map(|plan| plan.stages.len())
+        .max()
+        .unwrap_or_default();
+    let mut stages = (0..group_stages)
+        .map(|stage| {
+            group_plans
+                .iter()
+                .filter_map(|plan| plan.stages.get(stage))
+                .sum()
+        })
+        .collect::<Vec<_>>();
+    stages.extend(knockout.stages);
+    let group_rounds = group_plans
+        .iter()
+        .map(|plan| plan.rounds)
+        .max()
+        .unwrap_or_default();
+
+    Ok(Plan {
+        name: format!("{groups} groups + {}", Supported::SingleElimination.name()),
+        matches: stages.iter().sum(),
+        rounds: group_rounds + knockout.rounds,
+        stages,
+    })
+}
+
+/// planning a [`Supported`] format, see [`plan`]
+struct Planning {
+    name: String,
+    players: usize,
+}
+
+impl format::Run for Planning {
+    type Output = Result<Plan>;
+
+    fn run<F: Format>(self, format: F) -> Result<Plan> {
+        plan(self.name, format, self.players)
+    }
+}
+
+/// the [`Plan`] of `format` for `players` players, by playing it out
+fn plan<F: Format>(name: String, format: F, players: usize) -> Result<Plan> {
+    let players = Players(
+        (1..=players)
+            .map(|nr| Player {
+                name: nr.to_string(),
+                class: None,
+                nr: 0,
+            })
+            .collect(),
+    );
+    let mut tment = Tournament::new(format).with_players(players)?;
+    // the number of matches of every stage
+    let mut stages = Vec::new();
+    tment.set_quiet(true);
+    let mut rounds = 0;
+    while !tment.is_end() {
+        while let Some(matches) = tment.play_next_stage_with(true, |_| true)? {
+            stages.push(matches);
+        }
+        rounds += 1;
+    }
+
+    Ok(Plan {
+        name,
+        matches: tment.log().len(),
+        rounds,
+        stages,
+    })
+}
+
+/// the [`Plan`] with the most matches that can be played in `time` minutes: everyone plays more,
+/// the results are more reliable
+pub fn recommend(
+    plans: &[Plan],
+    tables: usize,
+    match_duration: usize,
+    time: usize,
+) -> Option<&Plan> {
+    plans
+        .iter()
+        .filter(|plan| plan.duration(tables, match_duration) <= time)
+        .max_by_key(|plan| plan.matches)
+}
+
+/// `minutes` as hours and minutes
+fn hours(minutes: usize) -> String {
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// plan the tournament of the participants in `file` with `tables` tables, matches lasting `match_duration` minutes,
+/// recommend a format if the `time` available in minutes is given
+pub fn execute(
+    file: &Path,
+    tables: usize,
+    match_duration: usize,
+    time: Option<usize>,
+) -> Result<()> {
+    let players = Players::load(file)?.0.len();
+    let plans = plans(players)?;
+
+    println!(
+        "\n{players} participants, {tables} tables, {match_duration} minutes per match\n\n{:<32} {:>7} {:>6} {:>8}",
+        "FORMAT", "MATCHES", "ROUNDS", "DURATION"
+    );
+    for plan in &plans {
+        let duration = plan.duration(tables, match_duration);
+        let fits = match time {
+            Some(time) if duration > time => "  too long",
+            _ => "",
+        };
+        println!(
+            "{:<32} {:>7} {:>6} {:>8}{fits}",
+            plan.name,
+            plan.matches,
+            plan.rounds,
+            hours(duration)
+        );
+    }
+    if let Some(time) = time {
+        match recommend(&plans, tables, match_duration, time) {
+            Some(plan) => println!("\nrecommended in {}: {}", hours(time), plan.name),
+            None => println!(
+                "\nnone of the formats fit in {}: you need more tables or time",
+                hours(time)
+            ),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn plans() {
+    let plans = super::plans(16).unwrap();
+    let find = |name: &str| plans.iter().find(|plan| plan.name == name).unwrap();
+
+    let round_robin = find("round-robin");
+    assert_eq!(120, round_robin.matches);
+    assert_eq!(15, round_robin.rounds);
+    assert_eq!(vec![8; 15], round_robin.stages);
+    // 8 matches at a time on 8 tables, 4 at a time on 4
+    assert_eq!(150, round_robin.duration(8, 10));
+    assert_eq!(300, round_robin.duration(4, 10));
+
+    let groups = find("4 groups + single-elimination");
+    // 4 groups of 4: 6 matches each, then a knockout of 8
+    assert_eq!(vec![8, 8, 8], groups.stages[..3]);
+    assert_eq!(4 * 6, groups.stages[..3].iter().sum::<usize>());
+    assert!(plans
+        .iter()
+        .all(|plan| plan.name != "8 groups + single-elimination"));
+
+    let single = find("single-elimination");
+    assert!(single.duration(8, 10) < groups.duration(8, 10));
+    assert_eq!(
+        Some(round_robin),
+        recommend(&plans, 8, 10, 150),
+        "the most matches that fit"
+    );
+    assert_eq!(None, recommend(&plans, 1, 10, 30));
+}
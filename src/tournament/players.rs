@@ -1,6 +1,39 @@
-use super::structs::*;
+use super::{structs::*, TournamentError};
 use std::path::Path;
 
+/// columns of the file with participants: the rest are unknown
+const COLUMNS: [&str; 6] = [
+    "name",
+    "class",
+    "rating",
+    "division",
+    "partner",
+    "partner_class",
+];
+
+/// a problem found in a file with participants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// the line it's found in, starting from 1: the header
+    pub line: u64,
+    pub message: String,
+}
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// a row of the file with participants, see [`Players::check_rows`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Row {
+    pub player: Player,
+    /// the `division` field
+    pub division: Option<String>,
+    /// the doubles partner: the `partner` and `partner_class` fields
+    pub partner: Option<Player>,
+}
+
 #[cfg(test)]
 pub mod tests;
 
@@ -9,13 +42,167 @@ pub struct Players(pub Vec<Player>);
 
 impl Players {
     /// load players from file at `path`
+    ///
+    /// rows with problems are left out, the problems are printed as warnings, see [`Self::check`]
     pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
-        let mut reader = csv::Reader::from_path(path)?;
-        let players = reader
-            .deserialize()
-            .flat_map(|x| x.inspect_err(|e| eprintln!("error: {e:#?}")))
-            .collect();
-        Ok(Self(players))
+        let (players, diagnostics) = Self::check(path)?;
+        for diagnostic in diagnostics {
+            eprintln!("warning: {diagnostic}");
+        }
+        Ok(players)
+    }
+    /// load players from file at `path`, refusing it if there's any problem with it, see [`Self::check`]
+    pub fn load_strict(path: impl AsRef<Path>) -> Result<Self, TournamentError> {
+        let (players, diagnostics) = Self::check(path)?;
+        if !diagnostics.is_empty() {
+            return Err(TournamentError::InvalidParticipants(diagnostics));
+        }
+        Ok(players)
+    }
+    /// load the [`Row`]s of the players from file at `path`: with problems printed as warnings,
+    /// or refused if `strict`, see [`Self::load`], [`Self::load_strict`]
+    pub(crate) fn load_rows(
+        path: impl AsRef<Path>,
+        strict: bool,
+    ) -> Result<Vec<Row>, TournamentError> {
+        let (rows, diagnostics) = Self::check_rows(path)?;
+        if strict && !diagnostics.is_empty() {
+            return Err(TournamentError::InvalidParticipants(diagnostics));
+        }
+        for diagnostic in diagnostics {
+            eprintln!("warning: {diagnostic}");
+        }
+        Ok(rows)
+    }
+    /// load players from file at `path`, leaving out rows with problems, and the problems found:
+    /// invalid classes, empty names, duplicate players, unknown columns and rows of a wrong length
+    ///
+    /// duplicate players are kept, they're told apart by their number, see [`Self::number_duplicates`]
+    pub fn check(path: impl AsRef<Path>) -> std::io::Result<(Self, Vec<Diagnostic>)> {
+        let (rows, diagnostics) = Self::check_rows(path)?;
+        let players = Self(rows.into_iter().map(|row| row.player).collect());
+        Ok((players, diagnostics))
+    }
+    /// [`Self::check`], but every player with the rest of it's [`Row`]: division and partner
+    pub(crate) fn check_rows(
+        path: impl AsRef<Path>,
+    ) -> std::io::Result<(Vec<Row>, Vec<Diagnostic>)> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_path(path)?;
+        let headers = reader.headers()?.clone();
+        let mut diagnostics = Vec::new();
+        let mut problem =
+            |line: u64, message: String| diagnostics.push(Diagnostic { line, message });
+
+        let column = |name: &str| headers.iter().position(|header| header.trim() == name);
+        for header in headers.iter().filter(|h| !COLUMNS.contains(&h.trim())) {
+            problem(1, format!("unknown column '{header}'"));
+        }
+        let Some(name_column) = column("name") else {
+            problem(1, "no 'name' column".into());
+            return Ok((Vec::new(), diagnostics));
+        };
+        let class_column = column("class");
+        let rating_column = column("rating");
+        let division_column = column("division");
+        let partner_column = column("partner");
+        let partner_class_column = column("partner_class");
+
+        // rows with the line they're in
+        let mut rows: Vec<(Row, u64)> = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |pos| pos.line());
+            if record.len() != headers.len() {
+                problem(
+                    line,
+                    format!("{} fields instead of {}", record.len(), headers.len()),
+                );
+            }
+            let field = |idx: Option<usize>| idx.and_then(|i| record.get(i)).map(str::trim);
+
+            let name = field(Some(name_column)).unwrap_or_default();
+            if name.is_empty() {
+                problem(line, "empty name".into());
+                continue;
+            }
+            let class = |column| match field(column).filter(|class| !class.is_empty()) {
+                None => Ok(None),
+                Some(class) => Class::try_from(class)
+                    .map(Some)
+                    .map_err(|e| format!("invalid class '{class}': {e}")),
+            };
+            let (class, partner_class) = match (class(class_column), class(partner_class_column)) {
+                (Ok(class), Ok(partner_class)) => (class, partner_class),
+                (Err(e), _) | (_, Err(e)) => {
+                    problem(line, e);
+                    continue;
+                }
+            };
+            if let Some(rating) = field(rating_column).filter(|rating| !rating.is_empty()) {
+                if rating.parse::<f64>().is_err() {
+                    problem(line, format!("invalid rating '{rating}'"));
+                }
+            }
+            let player = Player {
+                name: name.into(),
+                class,
+                nr: 0,
+            };
+            let mut same = rows.iter().filter(|(row, _)| row.player == player);
+            if let Some((_, first)) = same.next() {
+                let nr = same.count() + 2;
+                problem(
+                    line,
+                    format!("duplicate of line {first}: {player}, told apart as #{nr}"),
+                );
+            }
+            let division = field(division_column)
+                .filter(|division| !division.is_empty())
+                .map(String::from);
+            let partner = field(partner_column)
+                .filter(|partner| !partner.is_empty())
+                .map(|partner| Player {
+                    name: partner.into(),
+                    class: partner_class,
+                    nr: 0,
+                });
+            let row = Row {
+                player,
+                division,
+                partner,
+            };
+            rows.push((row, line));
+        }
+        let mut rows = rows.into_iter().map(|(row, _)| row).collect::<Vec<_>>();
+        let mut players = Self(rows.iter().map(|row| row.player.clone()).collect());
+        players.number_duplicates();
+        for (row, player) in rows.iter_mut().zip(players.0) {
+            row.player = player;
+        }
+        Ok((rows, diagnostics))
+    }
+    /// number players of the same name and class in their order, so that every one of them is unique,
+    /// see [`Player::nr`]
+    ///
+    /// already unique ones are left untouched
+    pub fn number_duplicates(&mut self) {
+        for i in 0..self.0.len() {
+            let same = |p: &Player| p.name == self.0[i].name && p.class == self.0[i].class;
+            let group = self.0.iter().filter(|p| same(p)).collect::<Vec<_>>();
+            let unique = group
+                .iter()
+                .enumerate()
+                .all(|(j, p)| group[..j].iter().all(|other| other.nr != p.nr));
+            if unique {
+                continue;
+            }
+            let indices = (0..self.0.len())
+                .filter(|&j| same(&self.0[j]))
+                .collect::<Vec<_>>();
+            for (nr, j) in indices.into_iter().enumerate() {
+                self.0[j].nr = nr + 1;
+            }
+        }
     }
     /// save `self` to file at `path`
     pub fn save(self, path: impl AsRef<Path>) -> std::io::Result<()> {
@@ -23,6 +210,29 @@ impl Players {
         self.0.iter().try_for_each(|p| writer.serialize(p))?;
         writer.flush()
     }
+    /// order `self`, ranked best first, into the standard bracket positions:
+    /// every two following players make up a [`Duel`], and the best ones meet as late as possible
+    ///
+    /// eg: 8 players: 1, 8, 4, 5, 2, 7, 3, 6
+    pub fn seed(&mut self) {
+        let mut positions = vec![1];
+        while positions.len() < self.0.len() {
+            let size = positions.len() * 2;
+            positions = positions
+                .into_iter()
+                .flat_map(|seed| [seed, size + 1 - seed])
+                .collect();
+        }
+        let mut ranked = std::mem::take(&mut self.0)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        // seeds missing from the bracket are skipped
+        self.0 = positions
+            .into_iter()
+            .filter_map(|seed| ranked.get_mut(seed - 1).and_then(Option::take))
+            .collect();
+    }
     /// `shuffle` and order, so that every two following players make up a [`Duel`]
     pub fn shuffle_as_pairs(&mut self) {
         // shuffle to make match-making unpredictable
@@ -38,8 +248,8 @@ impl Players {
             // current player
             let cnt = self.0.remove(0);
             // the least similar player's index
-            let idx = Self::diff_list(&self.0, &cnt)
-                .expect("possibly number of players isn't divisible by two");
+            // there's someone left: there's always one found
+            let idx = Self::diff_list(&self.0, &cnt).unwrap_or_default();
             as_pairs.push(cnt); // first the current player
             as_pairs.push(self.0.remove(idx)); // then the selected one
         }
@@ -79,6 +289,8 @@ impl Players {
     /// 2 same id
     /// 3 same grade
     /// 4 nothing in common (based on known things) cool!
+    ///
+    /// a player without a class has nothing in common with one with a class
     fn diff_list(haystack: &[Player], hay: &Player) -> Option<usize> {
         // no factor of difference: first one will be just fine
         if hay.class.is_none() {
@@ -89,15 +301,7 @@ impl Players {
         // calculate difference for all player
         // will break soon if highest difference factor is found
         for (i, p) in haystack.iter().enumerate() {
-            let diff = if hay.class == p.class {
-                1
-            } else if hay.class.unwrap().grade == p.class.unwrap().grade {
-                2
-            } else if hay.class.unwrap().id == p.class.unwrap().id {
-                3
-            } else {
-                4
-            };
+            let diff = Self::difference(hay, p);
             // update max if needed
             if diff > max.1 {
                 max.1 = diff; // value
@@ -110,4 +314,17 @@ impl Players {
         }
         max.0
     }
+    /// how different the classes of `a` and `b` are: from 1, the same class, to 4, nothing in common,
+    /// see [`Self::diff_list`]
+    pub(super) fn difference(a: &Player, b: &Player) -> u8 {
+        if a.class == b.class {
+            1
+        } else if a.class.map(|c| c.grade) == b.class.map(|c| c.grade) {
+            2
+        } else if a.class.map(|c| c.id) == b.class.map(|c| c.id) {
+            3
+        } else {
+            4
+        }
+    }
 }
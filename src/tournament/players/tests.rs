@@ -90,3 +90,83 @@ fn more_diff_list() {
         (nu_p("Expectant Wolfhound", 9, 'D'), Player::default())
     );
 }
+
+#[test]
+fn seed() {
+    let names = |players: &Players| players.0.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+    let ranked = |n: usize| Players((1..=n).map(|i| nu_p(&i.to_string(), 9, 'A')).collect());
+
+    let mut players = ranked(8);
+    players.seed();
+    assert_eq!(
+        ["1", "8", "4", "5", "2", "7", "3", "6"],
+        names(&players).as_slice()
+    );
+
+    let mut players = ranked(6);
+    players.seed();
+    assert_eq!(["1", "4", "5", "2", "3", "6"], names(&players).as_slice());
+}
+
+#[test]
+fn check() {
+    let path = std::env::temp_dir().join("tuna-man-check.csv");
+    std::fs::write(
+        &path,
+        "name,class,team\nAnna,9A,x\n,9B,x\nBob,B,x\nAnna,9A,x\nCecil,,x\nDan,10C\n",
+    )
+    .unwrap();
+    let (players, diagnostics) = Players::check(&path).unwrap();
+    assert_eq!(
+        Players(vec![
+            Player {
+                nr: 1,
+                ..nu_p("Anna", 9, 'A')
+            },
+            Player {
+                nr: 2,
+                ..nu_p("Anna", 9, 'A')
+            },
+            Player {
+                name: "Cecil".into(),
+                class: None,
+                nr: 0,
+            },
+            nu_p("Dan", 10, 'C'),
+        ]),
+        players
+    );
+    assert_eq!(
+        vec![
+            "line 1: unknown column 'team'",
+            "line 3: empty name",
+            "line 4: invalid class 'B': invalid grade number",
+            "line 5: duplicate of line 2: Anna, 9A, told apart as #2",
+            "line 7: 2 fields instead of 3",
+        ],
+        diagnostics
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    );
+    assert!(Players::load_strict(&path).is_err());
+    assert!(Players::load_strict("data.csv").is_ok());
+}
+
+#[test]
+fn number_duplicates() {
+    let mut players = Players(vec![
+        nu_p("Anna", 9, 'A'),
+        nu_p("Anna", 10, 'B'),
+        nu_p("Bob", 9, 'A'),
+        nu_p("Anna", 9, 'A'),
+    ]);
+    players.number_duplicates();
+    let nrs = players.0.iter().map(|p| p.nr).collect::<Vec<_>>();
+    assert_eq!(vec![1, 0, 0, 2], nrs);
+    assert_eq!("Anna, 9A #2", players.0[3].to_string());
+    // already unique
+    let numbered = players.clone();
+    players.number_duplicates();
+    assert_eq!(numbered, players);
+}
@@ -0,0 +1,260 @@
+use super::{bracket, format::Format, team, Duel, Player, Result, Tournament, TournamentError};
+use std::io::Write;
+
+/// the commands understood at the result prompt
+pub const HELP: &str = "\
+commands:
+    <id> <winner>, <winner>  enter a result, eg: `W1-3 anna`, `3 <`, `anna`
+    <heat> <ranking>         rank the players of a heat by their numbers or names, eg: `2 3 1 4`, `R1-2 cecil, anna`
+    status                   the brackets or standings and the duels of the actual stage
+    standings                the actual standings
+    bracket                  the brackets, or the crosstable if there are none
+    undo                     take back the last result entered in the actual stage
+    next, <enter>            go on to the next stage, once every result of the actual one is in
+    withdraw <name>          the player loses every match still to come: walkovers
+    enter <name>[, <class>]  a late entrant plays from the next round on, the ones missed are lost
+    tie <id>                 enter the lineups and the rubbers of a tie of teams, eg: `tie 3`
+    save                     save the log, crosstable, html page and svg now
+    help                     this help
+    q, quit, exit            save and quit
+a player named like a command can be entered with the id of the duel: `3 status`";
+
+/// what's been typed at the result prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Status,
+    Standings,
+    Bracket,
+    Undo,
+    /// the actual stage is confirmed
+    Next,
+    /// the name of the withdrawing player
+    Withdraw(String),
+    /// the late entrant: `<name>[, <class>]`
+    Enter(String),
+    /// the id of the tie
+    Tie(String),
+    Save,
+    Help,
+    Quit,
+    /// a result, see [`Duel::find_outcome`]
+    Result(String),
+}
+
+/// what's to be done after a [`Command`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// the actual stage goes on
+    Stay,
+    /// the actual stage is settled and confirmed, the next one can start
+    Next,
+    /// the [`Tournament`] shall be quit
+    Quit,
+}
+
+impl Command {
+    /// the [`Command`] in `input`, a [`Command::Result`] if it's none of the others
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+        let (command, rest) = input.split_once(' ').unwrap_or((input, ""));
+        match command.to_lowercase().as_str() {
+            "status" => Self::Status,
+            "standings" => Self::Standings,
+            "bracket" | "brackets" => Self::Bracket,
+            "undo" => Self::Undo,
+            "" | "next" | "n" => Self::Next,
+            "withdraw" => Self::Withdraw(rest.trim().into()),
+            "enter" => Self::Enter(rest.trim().into()),
+            "tie" => Self::Tie(rest.trim().into()),
+            "save" => Self::Save,
+            "help" | "?" => Self::Help,
+            "q" | "quit" | "exit" => Self::Quit,
+            _ => Self::Result(input.into()),
+        }
+    }
+}
+
+impl<F: Format> Tournament<F> {
+    /// execute what's typed at the result prompt, see [`Command`]
+    ///
+    /// the actual stage stays open till it's confirmed with [`Command::Next`], so its last result can be taken back
+    pub fn prompt(&mut self, input: &str) -> Result<Flow> {
+        match Command::parse(input) {
+            Command::Status => {
+                self.print_status();
+                self.print_duels();
+            }
+            Command::Standings => {
+                println!("\nSTANDINGS:\n");
+                for standing in self.standings() {
+                    println!(
+                        "    {}. {}: {}",
+                        standing.place, standing.player, standing.note
+                    );
+                }
+                println!();
+            }
+            Command::Bracket => match self.crosstable() {
+                Some(table) => println!("\n{}", table.render()),
+                None => bracket::print(&self.matches(), self.format.branches()),
+            },
+            Command::Undo => {
+                let heats = !self.format.heats().is_empty();
+                let duel = self.undo()?;
+                match heats {
+                    // every duel of the heat
+                    true => println!("taken back: {}", duel.id),
+                    false => println!("taken back: {}. {duel}", duel.id),
+                }
+            }
+            Command::Next if self.is_settled() => return Ok(Flow::Next),
+            Command::Next => {
+                return Err(TournamentError::InvalidInput(
+                    "not every result of the stage is in yet",
+                ))
+            }
+            Command::Withdraw(name) => {
+                let player = self.withdraw(&name)?;
+                println!("{player} has withdrawn, walkovers:");
+                for duel in self.duels.iter().filter(|duel| duel.has(&player)) {
+                    println!("    {}. {duel}", duel.id);
+                }
+            }
+            Command::Enter(input) => {
+                let player = self.enter_late(&input)?;
+                println!("{player} has entered, playing from the next round on");
+            }
+            Command::Tie(id) => {
+                let tie = self.read_tie(&id)?.clone();
+                if let Some(duel) = self.duels.iter().find(|duel| duel.id == tie.id) {
+                    println!("{}. {duel} ({tie})", duel.id);
+                }
+            }
+            Command::Save => {
+                self.save()?;
+                println!("saved");
+            }
+            Command::Help => println!("{HELP}"),
+            Command::Quit => return Ok(Flow::Quit),
+            Command::Result(input) if self.format.heats().is_empty() => {
+                let duel = self.enter_result(&input)?;
+                println!("{}. {duel}", duel.id);
+            }
+            Command::Result(input) => {
+                let heat = self.enter_ranking(&input)?;
+                println!("{heat}");
+            }
+        }
+        Ok(Flow::Stay)
+    }
+    /// read the lineups and the results of the rubbers of the tie with `id` from stdin, and enter them,
+    /// see [`Self::enter_tie`]
+    fn read_tie(&mut self, id: &str) -> Result<&team::Tie> {
+        let format = self
+            .tie_format
+            .clone()
+            .ok_or(TournamentError::InvalidInput("no ties in this tournament"))?;
+        let idx =
+            Duel::find(&self.duels, id).map_err(|e| TournamentError::InvalidInput(e.as_str()))?;
+        let duel = &self.duels[idx];
+        let (home_positions, away_positions) = format.positions();
+        let home = ask(&format!(
+            "lineup of {} ({}): ",
+            duel.homie,
+            format.lineup_hint(home_positions, true)
+        ))?;
+        let away = ask(&format!(
+            "lineup of {} ({}): ",
+            duel.guest,
+            format.lineup_hint(away_positions, false)
+        ))?;
+        let doubles = format.has_doubles();
+        let lineup = |team: &Player, input: &str, positions: usize| {
+            self.rosters
+                .lineup(team, input, positions, doubles)
+                .map_err(TournamentError::InvalidInput)
+        };
+        let tie = team::Tie {
+            id: duel.id,
+            home: lineup(&duel.homie, &home, home_positions)?,
+            away: lineup(&duel.guest, &away, away_positions)?,
+            results: Vec::new(),
+        };
+        for i in 0..format.0.len() {
+            let (home, away) = tie.rubber(&format, i);
+            println!("    {}. {home} vs {away}", i + 1);
+        }
+        let results =
+            ask("results of the rubbers till decided ('1'/'<' or '2'/'>', eg: `1 2 1 1`): ")?;
+        self.enter_tie(id, &home, &away, &results)
+    }
+    /// take back the last result entered in the actual stage: every [`Duel`] of a heat together
+    pub fn undo(&mut self) -> Result<&Duel> {
+        let idx = self.entered.pop().ok_or(TournamentError::InvalidInput(
+            "nothing to undo in this stage",
+        ))?;
+        let id = self.duels[idx].id;
+        self.entered.retain(|&i| self.duels[i].id != id);
+        for duel in self.duels.iter_mut().filter(|duel| duel.id == id) {
+            duel.outcome = None;
+        }
+        self.ties.retain(|tie| tie.id != id);
+        self.walkovers();
+        Ok(&self.duels[idx])
+    }
+    /// the [`Player`] `input` refers to withdraws: loses every match still to come
+    ///
+    /// `input`: parts of the name, class and number, see [`Duel::side`]
+    pub fn withdraw(&mut self, input: &str) -> Result<Player> {
+        let players = self
+            .standings()
+            .into_iter()
+            .map(|standing| standing.player)
+            .filter(|player| !self.withdrawn.contains(player))
+            .collect::<Vec<_>>();
+        let fitting = players
+            .iter()
+            .filter(|player| player.fits(input))
+            .collect::<Vec<_>>();
+        let player = match fitting.as_slice() {
+            [] => return Err(TournamentError::InvalidInput("no such player")),
+            [player] => (*player).clone(),
+            _ => {
+                return Err(TournamentError::InvalidInput(
+                    "ambiguous player, type more of the name",
+                ))
+            }
+        };
+        self.withdrawn.push(player.clone());
+        self.walkovers();
+        Ok(player)
+    }
+    /// the opponents of withdrawn players win the unsettled [`Duel`]s of the actual stage
+    pub(super) fn walkovers(&mut self) {
+        for duel in self.duels.iter_mut().filter(|duel| duel.outcome.is_none()) {
+            if self.withdrawn.contains(&duel.homie) {
+                duel.outcome = Some(false);
+            } else if self.withdrawn.contains(&duel.guest) {
+                duel.outcome = Some(true);
+            }
+        }
+    }
+}
+
+/// print `question` and read the answer from stdin
+///
+/// # Errors
+///
+/// at the end of input
+pub(super) fn ask(question: &str) -> Result<String> {
+    print!("{question}");
+    std::io::stdout().flush()?;
+    let mut buf = String::new();
+    match std::io::stdin().read_line(&mut buf)? {
+        0 => Err(TournamentError::InvalidInput("no answer")),
+        _ => Ok(buf.trim().into()),
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn parse() {
+    assert_eq!(Command::Status, Command::parse(" status\n"));
+    assert_eq!(Command::Bracket, Command::parse("Bracket"));
+    assert_eq!(
+        Command::Withdraw("kovács anna".into()),
+        Command::parse("withdraw kovács anna")
+    );
+    assert_eq!(Command::Quit, Command::parse("q"));
+    assert_eq!(Command::Next, Command::parse("\n"));
+    assert_eq!(
+        Command::Result("3 status".into()),
+        Command::parse("3 status")
+    );
+    assert_eq!(Command::Result("anna".into()), Command::parse("anna"));
+}
+
+#[test]
+fn undo_and_withdraw() {
+    let mut tment = Tournament::new(crate::tournament::format::RoundRobin::default())
+        .players_from_path("data.csv")
+        .unwrap();
+    let duels = tment.format.next_duels(false).unwrap();
+    tment.start_stage(duels);
+    assert!(tment.undo().is_err());
+    assert_eq!(Flow::Stay, tment.prompt("1 <").unwrap());
+    assert_eq!(Flow::Stay, tment.prompt("2 >").unwrap());
+    assert_eq!(2, tment.undo().unwrap().id.nr);
+    assert_eq!(1, tment.undo().unwrap().id.nr);
+    assert!(tment.duels[..2].iter().all(|duel| duel.outcome.is_none()));
+
+    assert!(tment.withdraw("nobody").is_err());
+    let player = tment.withdraw("central").unwrap();
+    assert_eq!("Central Mite", player.name);
+    assert!(tment.withdraw("central").is_err(), "already withdrawn");
+    // homies win, but the withdrawn player loses every match
+    tment.play_out(true, |_| true).unwrap();
+    let played = tment.log().iter().filter(|duel| duel.has(&player)).count();
+    assert_eq!(8, played);
+    for duel in tment.log().iter().filter(|duel| duel.has(&player)) {
+        assert_ne!(player, duel.clone().into_result().unwrap().0);
+    }
+    assert_eq!(Flow::Quit, tment.prompt("quit").unwrap());
+}
+
+#[test]
+fn undo_last_of_stage() {
+    let mut tment = Tournament::new(crate::tournament::format::RoundRobin::default())
+        .players_from_path("data.csv")
+        .unwrap();
+    let duels = tment.format.next_duels(false).unwrap();
+    tment.start_stage(duels);
+    assert!(tment.prompt("next").is_err(), "nothing's been entered yet");
+    for nr in 1..=4 {
+        assert_eq!(Flow::Stay, tment.prompt(&format!("{nr} <")).unwrap());
+    }
+    assert!(tment.is_settled());
+    // the typo in the last result is still taken back: the stage is open
+    assert_eq!(4, tment.undo().unwrap().id.nr);
+    assert!(tment.log().is_empty());
+    assert!(tment.prompt("").is_err());
+    assert_eq!(Flow::Stay, tment.prompt("4 >").unwrap());
+    assert_eq!(Flow::Next, tment.prompt("").unwrap());
+    tment.finish_stage().unwrap();
+    assert_eq!(Some(false), tment.log()[3].outcome);
+}
@@ -0,0 +1,88 @@
+use super::structs::{Duel, Player};
+use crate::rating::Elo;
+use std::{collections::HashMap, path::Path};
+
+/// rating of players without one in [`Outcomes::Rated`]
+const DEFAULT_RATING: f64 = 1500.;
+
+/// how the winners of simulated [`Duel`]s are chosen
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Outcomes {
+    /// both players win with the same probability
+    #[default]
+    Uniform,
+    /// the better rated one is more likely to win, see [`Elo::expected`]: ratings of players by their name
+    Rated(HashMap<String, f64>),
+}
+
+/// the way of choosing winners of a simulated [`super::Tournament`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Simulation {
+    /// both players win with the same probability
+    Uniform,
+    /// by win probability from the 'rating' column of the file with participants
+    Rating,
+}
+
+impl Outcomes {
+    /// [`Self::Rated`] from the `name` and `rating` columns of the csv file at `path`
+    ///
+    /// players with an empty or invalid rating are left out
+    pub fn rated_from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut reader = csv::Reader::from_path(&path)?;
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| headers.iter().position(|header| header.trim() == name);
+        let (Some(name), Some(rating)) = (column("name"), column("rating")) else {
+            return Err(std::io::Error::other(format!(
+                "no 'name' and 'rating' columns in {}",
+                path.as_ref().display()
+            )));
+        };
+        let mut ratings = HashMap::new();
+        for record in reader.records() {
+            let record = record?;
+            if let (Some(name), Some(Ok(rating))) = (
+                record.get(name),
+                record.get(rating).map(|r| r.trim().parse::<f64>()),
+            ) {
+                ratings.insert(name.to_string(), rating);
+            }
+        }
+        Ok(Self::Rated(ratings))
+    }
+    /// the rating of `player`: the same for everyone if [`Self::Uniform`]
+    pub fn rating(&self, player: &Player) -> f64 {
+        match self {
+            Self::Uniform => DEFAULT_RATING,
+            Self::Rated(ratings) => ratings.get(&player.name).copied().unwrap_or(DEFAULT_RATING),
+        }
+    }
+    /// the probability of the homie winning `duel`
+    pub fn homie_wins(&self, duel: &Duel) -> f64 {
+        self.homie_wins_by(duel, |player| vec![player.clone()])
+    }
+    /// [`Self::homie_wins`], but participants are made up of their `members`, eg: pairs of doubles,
+    /// with the average rating of them
+    pub fn homie_wins_by(&self, duel: &Duel, members: impl Fn(&Player) -> Vec<Player>) -> f64 {
+        let rating = |participant: &Player| {
+            let members = members(participant);
+            let sum = members
+                .iter()
+                .map(|member| self.rating(member))
+                .sum::<f64>();
+            sum / members.len().max(1) as f64
+        };
+        Elo::expected(rating(&duel.homie), rating(&duel.guest))
+    }
+    /// choose the outcome of `duel` randomly: `true` if the homie wins
+    pub fn decide(&self, duel: &Duel) -> bool {
+        fastrand::f64() < self.homie_wins(duel)
+    }
+    /// [`Self::decide`], but participants are made up of their `members`, see [`Self::homie_wins_by`]
+    pub fn decide_by(&self, duel: &Duel, members: impl Fn(&Player) -> Vec<Player>) -> bool {
+        fastrand::f64() < self.homie_wins_by(duel, members)
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
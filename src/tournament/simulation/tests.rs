@@ -0,0 +1,18 @@
+use super::*;
+use crate::tournament::players::tests::nu_p;
+
+#[test]
+fn outcomes() {
+    let duel = Duel::new(nu_p("Anna", 9, 'A'), nu_p("Bob", 9, 'B'));
+    assert_eq!(0.5, Outcomes::Uniform.homie_wins(&duel));
+
+    let rated = Outcomes::Rated([("Anna".to_string(), 1900.)].into());
+    // Bob's got the default rating
+    assert!((rated.homie_wins(&duel) - 0.909).abs() < 0.001);
+
+    let path = std::env::temp_dir().join("tuna-man-rated.csv");
+    std::fs::write(&path, "name,class,rating\nAnna,9A,1900\nBob,9B,\n").unwrap();
+    assert_eq!(rated, Outcomes::rated_from_path(&path).unwrap());
+    std::fs::write(&path, "name,class\nAnna,9A\n").unwrap();
+    assert!(Outcomes::rated_from_path(&path).is_err());
+}
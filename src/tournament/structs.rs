@@ -1,7 +1,5 @@
-use super::players::Players;
+use super::{players::Players, TournamentError};
 use serde::{Deserialize, Serialize};
-#[cfg(not(test))]
-use std::io::Write;
 
 /// a player/contestant/participant/team of a [`super::Tournament`]
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default, Hash)]
@@ -10,12 +8,18 @@ pub struct Player {
     pub name: String,
     /// class of player
     pub class: Option<Class>,
+    /// tells apart players with the same name and class, starting from 1, 0 if there's only one of them
+    ///
+    /// name, class and number make up the unique id of the Player
+    #[serde(skip)]
+    pub nr: usize,
 }
 impl Player {
     pub fn new(name: impl AsRef<str>, class: Class) -> Self {
         Self {
             name: name.as_ref().into(),
             class: Some(class),
+            nr: 0,
         }
     }
     /// not yet initialized
@@ -23,6 +27,34 @@ impl Player {
     pub fn is_unset(&self) -> bool {
         self == &Self::default()
     }
+    /// every word of `input` is part of the name, class or number of `self`,
+    /// or `input` is the initials of it's name, accents and case don't matter
+    pub fn fits(&self, input: &str) -> bool {
+        let input = normalize(input);
+        let name = normalize(&self.to_string());
+        let initials = normalize(&self.name)
+            .split(' ')
+            .filter_map(|word| word.chars().next())
+            .collect::<String>();
+        !input.is_empty() && (input == initials || input.split(' ').all(|word| name.contains(word)))
+    }
+}
+impl TryFrom<&str> for Player {
+    type Error = &'static str;
+
+    /// `<name>` or `<name>, <class>`, eg: `Kovács Anna, 9A`
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (name, class) = value.split_once(',').unwrap_or((value, ""));
+        let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if name.is_empty() {
+            return Err("empty name");
+        }
+        let class = match class.trim() {
+            "" => None,
+            class => Some(Class::try_from(class)?),
+        };
+        Ok(Self { name, class, nr: 0 })
+    }
 }
 impl std::fmt::Display for Player {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -39,7 +71,11 @@ impl std::fmt::Display for Player {
             } else {
                 String::new()
             }
-        )
+        )?;
+        if self.nr != 0 {
+            write!(f, " #{}", self.nr)?;
+        }
+        Ok(())
     }
 }
 
@@ -84,9 +120,143 @@ impl std::fmt::Display for Class {
     }
 }
 
+/// why a [`Duel`] or it's winner couldn't be found, see [`Duel::side`], [`Duel::find`], [`Duel::find_outcome`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FindError {
+    /// no winner's been given
+    NoWinner,
+    /// the input isn't an id at all
+    InvalidId,
+    /// there's no [`Duel`] with the id
+    NoDuel,
+    /// none of the players fits the input
+    NoPlayer,
+    /// both players of the [`Duel`] fit the input
+    BothFit,
+    /// players of more [`Duel`]s fit the input
+    Ambiguous,
+}
+impl FindError {
+    /// what's gone wrong, to be shown to the user
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NoWinner => "no winner given",
+            Self::InvalidId => "invalid id",
+            Self::NoDuel => "no duel with such id",
+            Self::NoPlayer => "no such player",
+            Self::BothFit => "ambiguous winner: both players fit, type more of the name",
+            Self::Ambiguous => "ambiguous winner, use the id of the duel as well",
+        }
+    }
+}
+impl std::fmt::Display for FindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// `text` in lowercase without accents, commas and repeated whitespace, to be matched against
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c == ',' { ' ' } else { unaccented(c) })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `c` without it's accent, if it's a lowercase latin letter with one
+fn unaccented(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'ď' => 'd',
+        'é' | 'è' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => 'e',
+        'í' | 'ì' | 'î' | 'ï' | 'ī' => 'i',
+        'ľ' | 'ĺ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ő' | 'ø' | 'ō' => 'o',
+        'ŕ' | 'ř' => 'r',
+        'ś' | 'š' | 'ş' => 's',
+        'ť' | 'ţ' => 't',
+        'ú' | 'ù' | 'û' | 'ü' | 'ű' | 'ū' | 'ů' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    }
+}
+
+/// the part of a [`super::Tournament`] a [`Duel`] is played in
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub enum Branch {
+    /// the one and only branch: single-elimination, round-robin
+    #[default]
+    Round,
+    /// winner branch of double-elimination
+    Winner,
+    /// loser branch of double-elimination
+    Loser,
+    /// duel for the third place
+    ThirdPlace,
+    /// grand final: the winners of the winner and loser branch
+    Final,
+}
+
+/// identifier of a [`Duel`], stable throughout the whole [`super::Tournament`]
+///
+/// format: `<branch><round>-<nr>`, eg: `W1-3`: winner branch, round 1, match 3,
+/// except for the grand final: `GF` and the third place duel: `3P`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct MatchId {
+    pub branch: Branch,
+    /// the round within `branch`, starting from 1, 0 if not yet given
+    pub round: usize,
+    /// number of the match within `round`, starting from 1, 0 if not yet given
+    pub nr: usize,
+}
+impl MatchId {
+    pub fn new(branch: Branch, round: usize, nr: usize) -> Self {
+        Self { branch, round, nr }
+    }
+}
+impl std::fmt::Display for MatchId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let branch = match self.branch {
+            Branch::Final => return write!(f, "GF"),
+            Branch::ThirdPlace => return write!(f, "3P"),
+            Branch::Round => 'R',
+            Branch::Winner => 'W',
+            Branch::Loser => 'L',
+        };
+        write!(f, "{branch}{}-{}", self.round, self.nr)
+    }
+}
+impl std::str::FromStr for MatchId {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_uppercase();
+        let branch = match s.as_str() {
+            "GF" => return Ok(Self::new(Branch::Final, 1, 1)),
+            "3P" => return Ok(Self::new(Branch::ThirdPlace, 1, 1)),
+            s if s.starts_with('R') => Branch::Round,
+            s if s.starts_with('W') => Branch::Winner,
+            s if s.starts_with('L') => Branch::Loser,
+            _ => return Err("invalid branch of match id"),
+        };
+        let (round, nr) = s[1..].split_once('-').ok_or("invalid match id")?;
+        let round = round.parse().map_err(|_| "invalid round of match id")?;
+        let nr = nr.parse().map_err(|_| "invalid number of match id")?;
+        Ok(Self { branch, round, nr })
+    }
+}
+
 /// A Duel/Match between two players.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Duel {
+    /// identifier of the duel, see [`MatchId`]
+    pub id: MatchId,
     pub homie: Player,
     pub guest: Player,
     /// homie won: true, opponent won: false
@@ -113,105 +283,123 @@ impl Duel {
         Self {
             homie,
             guest,
-            outcome: None,
+            ..Default::default()
         }
     }
+    /// `player` plays in `self`
+    pub fn has(&self, player: &Player) -> bool {
+        &self.homie == player || &self.guest == player
+    }
     /// `self` but with `outcome`
     pub fn with_outcome(self, outcome: Option<bool>) -> Self {
         Self { outcome, ..self }
     }
-    #[cfg(test)]
-    pub fn get_outcome(&mut self) -> Result<(), ()> {
-        self.outcome = Some(true);
-        Ok(())
-    }
-    #[cfg(not(test))]
-    pub fn get_outcome(&mut self) -> Result<(), ()> {
-        print!("winner: ");
-        std::io::stdout().flush().map_err(|_| ())?;
-        let mut buf = String::new();
-        std::io::stdin().read_line(&mut buf).map_err(|_| ())?;
-        let outcome = match buf.trim() {
-            "<" | "homie" => Some(true),
-            ">" | "guest" => Some(false),
-            name => {
-                let name = name.to_lowercase();
-                if self.homie.name.to_lowercase().contains(&name) {
-                    Some(true)
-                } else if self.guest.name.to_lowercase().contains(&name) {
-                    Some(false)
-                } else {
-                    // dbg!(&name);
-                    if matches!(name.as_str(), "q" | "quit" | "exit") {
-                        std::process::exit(0);
-                    }
-                    return Err(());
-                }
-            }
-        };
-        // println!("{self}");
-        self.outcome = outcome;
-        Ok(())
-    }
-    /// take winner of the game
+    /// the side of the winner `input` refers to: homie: true, guest: false
     ///
-    /// # Note
+    /// `input` may be `<`/`1`/`homie`, `>`/`2`/`guest` or parts of the winner's name, class and number,
+    /// eg: `anna 9a` or `anna #2` if there are more of them, or the initials of the name, eg: `ka` for Kovács Anna,
+    /// accents don't matter: `kovacs` is just as good
     ///
-    /// it's taken: moved and replaced by [`Player::default()`]
+    /// # Errors
     ///
-    /// # Panics
-    ///
-    /// if there's no outcome yet
-    fn take_winner(&mut self) -> Player {
-        if self.outcome.unwrap() {
-            std::mem::take(&mut self.homie)
-        } else {
-            std::mem::take(&mut self.guest)
+    /// if `input` fits none or both of the players
+    pub fn side(&self, input: &str) -> Result<bool, FindError> {
+        match input.trim() {
+            "<" | "1" | "homie" => Ok(true),
+            ">" | "2" | "guest" => Ok(false),
+            input => {
+                let input = normalize(input);
+                match (self.homie.fits(&input), self.guest.fits(&input)) {
+                    (true, false) => Ok(true),
+                    (false, true) => Ok(false),
+                    (false, false) => Err(FindError::NoPlayer),
+                    // eg: Bob and Bobby: the one with exactly that name
+                    (true, true) if normalize(&self.homie.name) == input => Ok(true),
+                    (true, true) if normalize(&self.guest.name) == input => Ok(false),
+                    (true, true) => Err(FindError::BothFit),
+                }
+            }
         }
     }
-    /// take loser of the game
-    ///
-    /// # Note
+    /// find the [`Duel`] in `duels` with the id `input`: either a [`MatchId`] or just the number of the match
     ///
-    /// it's taken: moved and replaced by [`Player::default()`]
+    /// returns it's index in `duels`
+    pub fn find(duels: &[Duel], input: &str) -> Result<usize, FindError> {
+        let input = input.trim();
+        let position = if let Ok(id) = input.parse::<MatchId>() {
+            duels.iter().position(|duel| duel.id == id)
+        } else if let Ok(nr) = input.parse::<usize>() {
+            duels.iter().position(|duel| duel.id.nr == nr)
+        } else {
+            return Err(FindError::InvalidId);
+        };
+        position.ok_or(FindError::NoDuel)
+    }
+    /// find the [`Duel`] in `duels` that `input` refers to, and it's outcome
     ///
-    /// # Panics
+    /// `input` is either `<id> <winner>` or just `<winner>`, for `<winner>` see [`Self::side`]
+    /// `<id>` is either a [`MatchId`] or just the number of the match
     ///
-    /// if there's no outcome yet
-    fn take_loser(&mut self) -> Player {
-        if self.outcome.unwrap() {
-            std::mem::take(&mut self.guest)
-        } else {
-            std::mem::take(&mut self.homie)
+    /// returns the index of the [`Duel`] in `duels` and the outcome
+    pub fn find_outcome(duels: &[Duel], input: &str) -> Result<(usize, bool), FindError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(FindError::NoWinner);
         }
-    }
-
-    /// play the [`Duel`]: get an outcome with `read_outcome`
-    pub fn play(mut self) -> (Player, Player) {
-        loop {
-            if let Ok(()) = self.get_outcome() {
-                return (self.take_winner(), self.take_loser());
+        // with id
+        if let Some((id, winner)) = input.split_once(char::is_whitespace) {
+            match Self::find(duels, id) {
+                Err(FindError::InvalidId) => {}
+                idx => {
+                    let idx = idx?;
+                    let outcome = duels[idx].side(winner)?;
+                    return Ok((idx, outcome));
+                }
+            }
+        }
+        // just the winner
+        let sides = duels
+            .iter()
+            .map(|duel| duel.side(input))
+            .collect::<Vec<_>>();
+        let found = sides
+            .iter()
+            .enumerate()
+            .filter_map(|(i, side)| side.map(|outcome| (i, outcome)).ok())
+            .collect::<Vec<_>>();
+        match found.as_slice() {
+            [] if sides.contains(&Err(FindError::BothFit)) => Err(FindError::BothFit),
+            [] => Err(FindError::NoPlayer),
+            [found] => Ok(*found),
+            _ => {
+                // prefer the ones not yet settled
+                let mut unsettled = found.iter().filter(|(i, _)| duels[*i].outcome.is_none());
+                match (unsettled.next(), unsettled.next()) {
+                    (Some(found), None) => Ok(*found),
+                    _ => Err(FindError::Ambiguous),
+                }
             }
-            println!("invalid input");
         }
     }
-    /// # Info
+    /// the result of the played [`Duel`]: (winner, loser)
     ///
-    /// - creates [`Duel`] from first two [`Player`]s of `branch`
-    /// - plays the [`Duel`]
-    /// - winner get's pushed back to the `branch`
-    /// - loser get's returned
+    /// # Errors
     ///
-    /// # Warning
+    /// if there's no outcome yet
+    pub fn into_result(self) -> Result<(Player, Player), TournamentError> {
+        match self.outcome {
+            Some(true) => Ok((self.homie, self.guest)),
+            Some(false) => Ok((self.guest, self.homie)),
+            None => Err(TournamentError::NoOutcome(self.id)),
+        }
+    }
+    /// # Info
     ///
-    /// there's a `println!()` hidden in here
-    pub fn handle_special(branch: &mut Players) -> Player {
+    /// creates [`Duel`] from first two [`Player`]s of `branch`,
+    /// the winner shall be pushed back to the `branch` once it's played
+    pub fn special(branch: &mut Players) -> Self {
         let (homie, guest) = (branch.0.remove(0), branch.0.swap_remove(0)); // remove first two
-        let duel = Duel::new(homie, guest); // create a duel
-        println!("{duel}");
-        let (winner, loser) = duel.play(); // play it
-        branch.0.push(winner); // winner stays
-        loser
+        Duel::new(homie, guest)
     }
 }
 
@@ -224,4 +412,121 @@ mod tests {
         let exp = Class::new(0, 'A');
         assert_eq!(Ok(exp), "00A".try_into());
     }
+
+    #[test]
+    fn player_from() {
+        let anna = Player::new("Kovács Anna", Class::new(9, 'A'));
+        assert_eq!(Ok(anna), " Kovács  Anna , 9A".try_into());
+        let bob = Player {
+            name: "Bob".into(),
+            ..Default::default()
+        };
+        assert_eq!(Ok(bob), "Bob".try_into());
+        assert_eq!(Err("empty name"), Player::try_from(" , 9A"));
+        assert!(Player::try_from("Bob, A").is_err());
+    }
+
+    #[test]
+    fn match_id() {
+        let ids = [
+            ("W1-3", MatchId::new(Branch::Winner, 1, 3)),
+            ("L12-1", MatchId::new(Branch::Loser, 12, 1)),
+            ("R4-2", MatchId::new(Branch::Round, 4, 2)),
+            ("GF", MatchId::new(Branch::Final, 1, 1)),
+            ("3P", MatchId::new(Branch::ThirdPlace, 1, 1)),
+        ];
+        for (s, id) in ids {
+            assert_eq!(s, id.to_string());
+            assert_eq!(Ok(id), s.parse());
+            assert_eq!(Ok(id), s.to_lowercase().parse());
+        }
+        assert!("X1-1".parse::<MatchId>().is_err());
+        assert!("W1".parse::<MatchId>().is_err());
+        assert!("W-1".parse::<MatchId>().is_err());
+    }
+
+    #[test]
+    fn find_outcome() {
+        let duel = |nr, homie, guest| Duel {
+            id: MatchId::new(Branch::Winner, 2, nr),
+            ..Duel::new(
+                Player::new(homie, Class::new(9, 'A')),
+                Player::new(guest, Class::new(10, 'B')),
+            )
+        };
+        let duels = [duel(1, "Anna", "Bob"), duel(2, "Cecil", "Bobby")];
+
+        assert_eq!(Ok((0, true)), Duel::find_outcome(&duels, "anna"));
+        assert_eq!(Ok((1, false)), Duel::find_outcome(&duels, "2 bobby"));
+        assert_eq!(Ok((1, true)), Duel::find_outcome(&duels, "2 <"));
+        assert_eq!(Ok((0, false)), Duel::find_outcome(&duels, " 1 > "));
+        assert_eq!(Err(FindError::Ambiguous), Duel::find_outcome(&duels, "bob"));
+        assert_eq!(Ok((1, false)), Duel::find_outcome(&duels, "w2-2 Bobby"));
+        assert_eq!(Err(FindError::NoDuel), Duel::find_outcome(&duels, "3 anna"));
+        assert_eq!(
+            Err(FindError::NoDuel),
+            Duel::find_outcome(&duels, "L2-1 anna")
+        );
+        assert_eq!(
+            Err(FindError::NoPlayer),
+            Duel::find_outcome(&duels, "1 cecil")
+        );
+        assert_eq!(Err(FindError::NoWinner), Duel::find_outcome(&duels, ""));
+        assert_eq!(Err(FindError::InvalidId), Duel::find(&duels, "first"));
+
+        let duels = [duels[0].clone().with_outcome(Some(true)), duels[1].clone()];
+        assert_eq!(Ok((1, false)), Duel::find_outcome(&duels, "bob"));
+    }
+
+    #[test]
+    fn same_names() {
+        let anna = |grade, id, nr| Player {
+            nr,
+            ..Player::new("Kovács Anna", Class::new(grade, id))
+        };
+        let duel = Duel::new(anna(9, 'A', 0), anna(10, 'B', 0));
+        assert_eq!(Err(FindError::BothFit), duel.side("anna"));
+        assert_eq!(Ok(false), duel.side("Anna, 10B"));
+        assert_eq!(Ok(true), duel.side("kovács anna 9a"));
+
+        let duel = Duel::new(anna(9, 'A', 1), anna(9, 'A', 2));
+        assert_eq!(
+            "Kovács Anna, 9A #2 <-> Kovács Anna, 9A #1",
+            Duel::new(duel.guest.clone(), duel.homie.clone()).to_string()
+        );
+        assert_eq!(Ok(false), duel.side("anna #2"));
+        assert!(duel.side("anna 9a").is_err());
+
+        let duel = Duel::new(
+            Player::new("Bobby", Class::new(9, 'A')),
+            Player::new("Bob", Class::new(9, 'B')),
+        );
+        assert_eq!(Ok(false), duel.side("bob"));
+    }
+
+    #[test]
+    fn ambiguity() {
+        let duel = Duel::new(
+            Player::new("Anna", Class::new(9, 'A')),
+            Player::new("Johan", Class::new(9, 'B')),
+        );
+        assert_eq!(Err(FindError::BothFit), duel.side("an"));
+        assert_eq!(
+            Err(FindError::BothFit),
+            Duel::find_outcome(std::slice::from_ref(&duel), "an")
+        );
+        assert_eq!(Ok(true), duel.side("1"));
+        assert_eq!(Ok(false), duel.side("2"));
+        assert_eq!(Ok(false), duel.side("joh"));
+
+        let duel = Duel::new(
+            Player::new("Kovács Anna", Class::new(9, 'A')),
+            Player::new("Szőke Ödön", Class::new(9, 'B')),
+        );
+        assert_eq!(Ok(true), duel.side("kovacs"));
+        assert_eq!(Ok(false), duel.side("SZOKE odon"));
+        assert_eq!(Ok(true), duel.side("ka"));
+        assert_eq!(Ok(false), duel.side("sző"));
+        assert_eq!(Err(FindError::NoPlayer), duel.side("bob"));
+    }
 }
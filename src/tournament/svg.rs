@@ -0,0 +1,233 @@
+use super::{
+    bracket::Bracket,
+    html::escape,
+    structs::{Branch, Duel, Player},
+};
+use std::fmt::Write;
+
+/// height of a line of a [`Bracket`]
+const LINE: f64 = 8.;
+/// width of a round of a [`Bracket`]
+const COLUMN: f64 = 90.;
+/// font size of names
+const FONT: f64 = 5.;
+/// font size of ids and labels
+const SMALL_FONT: f64 = 3.5;
+/// height of the title of a [`Bracket`]
+const TITLE: f64 = 14.;
+/// margin of the page in mm
+const MARGIN: f64 = 10.;
+
+/// size of the paper the svg is made for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Paper {
+    #[default]
+    A4,
+    A3,
+}
+impl Paper {
+    /// width and height in mm, portrait
+    pub fn size(self) -> (f64, f64) {
+        match self {
+            Self::A4 => (210., 297.),
+            Self::A3 => (297., 420.),
+        }
+    }
+}
+
+/// what's written to the line of a player in a [`Duel`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Slot {
+    /// a known player, `true` if they've won
+    Name(String, bool),
+    /// where the player is coming from, eg: `Loser of W1-3`
+    Label(String),
+    /// no opponent
+    Bye,
+}
+
+/// where `player` of `plan[k]` is coming from: the previous [`Duel`] they've played in
+fn source<'a>(plan: &'a [Duel], k: usize, player: &Player) -> Option<&'a Duel> {
+    plan[..k]
+        .iter()
+        .rev()
+        .find(|duel| !duel.guest.is_unset() && (&duel.homie == player || &duel.guest == player))
+}
+
+/// the [`Slot`]s of `plan[k]`
+///
+/// - `known`: [`Duel`]s really played, pending ones and byes, `None` if only the first round is known
+fn slots(plan: &[Duel], k: usize, known: Option<&[Duel]>) -> (Slot, Slot) {
+    let duel = &plan[k];
+    let bye = duel.guest.is_unset();
+    let known_duel = known.and_then(|known| {
+        known.iter().find(|kd| {
+            kd.id == duel.id && kd.guest.is_unset() == bye && (!bye || kd.homie == duel.homie)
+        })
+    });
+    if let Some(kd) = known_duel {
+        let homie = Slot::Name(kd.homie.to_string(), kd.outcome == Some(true) && !bye);
+        let guest = if bye {
+            Slot::Bye
+        } else {
+            Slot::Name(kd.guest.to_string(), kd.outcome == Some(false))
+        };
+        return (homie, guest);
+    }
+    let first_round = plan.first().map(|first| first.id);
+    let is_first_round = first_round
+        .is_some_and(|first| first.branch == duel.id.branch && duel.id.round == first.round);
+
+    let slot = |player: &Player| {
+        let Some(src) = source(plan, k, player) else {
+            return Slot::Name(player.to_string(), false);
+        };
+        if is_first_round && known.is_none() {
+            return Slot::Name(player.to_string(), false);
+        }
+        let won = src.outcome == Some(src.homie == *player);
+        // the source is already known: so is the player
+        let known_src = known.and_then(|known| {
+            known
+                .iter()
+                .find(|kd| kd.id == src.id && kd.outcome.is_some())
+        });
+        if let Some(ks) = known_src {
+            let player = if won == ks.outcome.unwrap() {
+                &ks.homie
+            } else {
+                &ks.guest
+            };
+            return Slot::Name(player.to_string(), false);
+        }
+        Slot::Label(format!(
+            "{} of {}",
+            if won { "Winner" } else { "Loser" },
+            src.id
+        ))
+    };
+    let homie = slot(&duel.homie);
+    let guest = if bye { Slot::Bye } else { slot(&duel.guest) };
+    (homie, guest)
+}
+
+/// the brackets of `branches` as an svg fitting onto `paper`
+///
+/// - `plan`: every [`Duel`] of the whole tournament in the order they're played, byes included,
+///   outcomes may be made up, they're only used to see where players are coming from
+/// - `known`: [`Duel`]s really played, pending ones and byes, `None` for an empty bracket:
+///   only the names of the first round are written
+pub fn render(
+    branches: &[(Branch, &str)],
+    plan: &[Duel],
+    known: Option<&[Duel]>,
+    paper: Paper,
+    landscape: bool,
+) -> String {
+    let mut content = String::new();
+    let (mut width, mut height) = (0_f64, 0_f64);
+
+    for (branch, title) in branches {
+        let bracket = Bracket::new(plan, *branch);
+        if bracket.is_empty() {
+            continue;
+        }
+        let places = bracket.places();
+        let lines = places
+            .iter()
+            .flatten()
+            .map(|p| p.bottom + 1)
+            .max()
+            .unwrap_or_default();
+        let _ = writeln!(
+            content,
+            "<text x=\"0\" y=\"{:.1}\" font-size=\"{}\" stroke=\"none\" font-weight=\"bold\">{}</text>",
+            height + TITLE - 4.,
+            FONT * 1.6,
+            escape(title)
+        );
+        let top = height + TITLE + LINE;
+
+        for (i, (round, places)) in bracket.rounds().iter().zip(&places).enumerate() {
+            let x = i as f64 * COLUMN;
+            let leads_on = i + 1 < bracket.rounds().len() && bracket.continues(i + 1);
+            for (duel, place) in round.iter().zip(places) {
+                // index of the duel in the plan
+                let k = plan
+                    .iter()
+                    .position(|d| std::ptr::eq(d, *duel))
+                    .unwrap_or_default();
+                let (homie, guest) = slots(plan, k, known);
+                let y = |line: usize| top + line as f64 * LINE;
+                draw_slot(&mut content, x, y(place.top), &homie);
+                draw_slot(&mut content, x, y(place.bottom), &guest);
+                let _ = writeln!(
+                    content,
+                    "<line x1=\"{x1:.1}\" y1=\"{:.1}\" x2=\"{x1:.1}\" y2=\"{:.1}\"/>",
+                    y(place.top),
+                    y(place.bottom),
+                    x1 = x + COLUMN - 8.,
+                );
+                if leads_on {
+                    let _ = writeln!(
+                        content,
+                        "<line x1=\"{:.1}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\"/>",
+                        x + COLUMN - 8.,
+                        x + COLUMN,
+                        y = y(place.mid),
+                    );
+                }
+                if guest != Slot::Bye {
+                    let _ = writeln!(
+                        content,
+                        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{SMALL_FONT}\" stroke=\"none\" fill=\"#666\" text-anchor=\"end\">{}</text>",
+                        x + COLUMN - 10.,
+                        (y(place.top) + y(place.bottom)) / 2. + SMALL_FONT / 2.,
+                        duel.id
+                    );
+                }
+            }
+        }
+        width = width.max(bracket.rounds().len() as f64 * COLUMN);
+        height = top + lines as f64 * LINE;
+    }
+
+    let (mut page_w, mut page_h) = paper.size();
+    if landscape {
+        std::mem::swap(&mut page_w, &mut page_h);
+    }
+    let scale =
+        ((page_w - 2. * MARGIN) / width.max(1.)).min((page_h - 2. * MARGIN) / height.max(1.));
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{page_w}mm\" height=\"{page_h}mm\" viewBox=\"0 0 {page_w} {page_h}\">
+<g transform=\"translate({MARGIN} {MARGIN}) scale({scale:.4})\" font-family=\"sans-serif\" stroke-width=\"0.4\">
+<g stroke=\"black\">
+{content}</g>
+</g>
+</svg>
+"
+    )
+}
+
+/// draw `slot` of a [`Duel`] onto the line at `y` in the round at `x`
+fn draw_slot(svg: &mut String, x: f64, y: f64, slot: &Slot) {
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{x:.1}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\"/>",
+        x + COLUMN - 8.
+    );
+    let (text, font, attrs) = match slot {
+        Slot::Name(name, true) => (name.as_str(), FONT, " font-weight=\"bold\""),
+        Slot::Name(name, false) => (name.as_str(), FONT, ""),
+        Slot::Label(label) => (label.as_str(), SMALL_FONT, " fill=\"#888\""),
+        Slot::Bye => ("bye", SMALL_FONT, " fill=\"#888\" font-style=\"italic\""),
+    };
+    let _ = writeln!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{font}\" stroke=\"none\"{attrs}>{}</text>",
+        x + 2.,
+        y - 1.5,
+        escape(text)
+    );
+}
@@ -0,0 +1,259 @@
+use super::structs::{MatchId, Player};
+use std::{collections::HashMap, path::Path};
+
+/// an individual match of a tie: positions in the singles lineups, home first, or the doubles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rubber {
+    Singles(usize, usize),
+    Doubles,
+}
+
+/// the rubbers making up a tie, in the order they're played: the first team to win most of them wins the tie
+///
+/// positions of the home team are named `A`, `B`, `C`, of the away team `X`, `Y`, `Z`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TieFormat(pub Vec<Rubber>);
+
+impl std::str::FromStr for TieFormat {
+    type Err = String;
+
+    /// a preset: `swaythling` (`AX,BY,CZ,AY,BX`) or `corbillon` (`AX,BY,D,AY,BX`),
+    /// or rubbers separated by commas, `D` for the doubles, eg: `AX,BY,D,AY,BX`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rubbers = match s.trim().to_lowercase().as_str() {
+            "swaythling" => "AX,BY,CZ,AY,BX".to_string(),
+            "corbillon" => "AX,BY,D,AY,BX".to_string(),
+            _ => s.to_uppercase(),
+        };
+        let rubbers = rubbers
+            .split(',')
+            .map(|rubber| {
+                let rubber = rubber.trim();
+                let mut chars = rubber.chars();
+                match (chars.next(), chars.next(), chars.next()) {
+                    (Some('D'), None, None) => Ok(Rubber::Doubles),
+                    (Some(home @ 'A'..='C'), Some(away @ 'X'..='Z'), None) => Ok(Rubber::Singles(
+                        home as usize - 'A' as usize,
+                        away as usize - 'X' as usize,
+                    )),
+                    _ => Err(format!(
+                        "invalid rubber '{rubber}', expected eg: 'AX' or 'D' for the doubles"
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if rubbers.len() % 2 == 0 {
+            return Err("an odd number of rubbers is needed for a winner".into());
+        }
+        Ok(Self(rubbers))
+    }
+}
+
+impl TieFormat {
+    /// number of singles players of the home and the away team
+    pub fn positions(&self) -> (usize, usize) {
+        self.0
+            .iter()
+            .fold((0, 0), |(home, away), rubber| match rubber {
+                Rubber::Singles(h, a) => (home.max(h + 1), away.max(a + 1)),
+                Rubber::Doubles => (home, away),
+            })
+    }
+    /// there's a doubles among the rubbers
+    pub fn has_doubles(&self) -> bool {
+        self.0.contains(&Rubber::Doubles)
+    }
+    /// number of rubbers a team has to win to win the tie
+    pub fn to_win(&self) -> usize {
+        self.0.len() / 2 + 1
+    }
+    /// how the lineup of a team with `positions` singles players is to be entered
+    pub fn lineup_hint(&self, positions: usize, home: bool) -> String {
+        let first = if home { 'A' } else { 'X' };
+        let mut hint = (0..positions)
+            .map(|i| char::from(first as u8 + i as u8).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.has_doubles() {
+            hint += " / doubles pair";
+        }
+        hint
+    }
+}
+
+/// the players of a team in a tie
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lineup {
+    /// in the order of their positions: `A`, `B`, `C` or `X`, `Y`, `Z`
+    pub singles: Vec<Player>,
+    /// empty if there's no doubles
+    pub doubles: Vec<Player>,
+}
+
+/// members of the teams by the names of the teams
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rosters(pub HashMap<String, Vec<Player>>);
+
+impl Rosters {
+    /// load rosters from the csv file at `path` with `team` and `name` columns: a row for every member
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        #[derive(serde::Deserialize)]
+        struct Row {
+            team: String,
+            name: String,
+        }
+        let mut rosters = Self::default();
+        for row in csv::Reader::from_path(path)?.deserialize::<Row>() {
+            let row = row?;
+            let member = Player {
+                name: row.name.trim().into(),
+                ..Default::default()
+            };
+            rosters
+                .0
+                .entry(row.team.trim().into())
+                .or_default()
+                .push(member);
+        }
+        Ok(rosters)
+    }
+    /// the lineup of `team` from `input`: names of the singles players in the order of their positions,
+    /// then after a `/` the doubles pair, all separated by commas, eg: `anna, bob, cecil / anna, bob`
+    ///
+    /// names are parts of the ones in the roster of `team`, see [`Player::fits`],
+    /// or anything if `team` has got no roster
+    pub fn lineup(
+        &self,
+        team: &Player,
+        input: &str,
+        positions: usize,
+        doubles: bool,
+    ) -> Result<Lineup, &'static str> {
+        let (singles, pair) = input.split_once('/').unwrap_or((input, ""));
+        let members = |input: &str| {
+            input
+                .split(',')
+                .filter(|name| !name.trim().is_empty())
+                .map(|name| self.member(team, name))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let (singles, pair) = (members(singles)?, members(pair)?);
+        if singles.len() != positions {
+            return Err("wrong number of singles players in the lineup");
+        }
+        if singles
+            .iter()
+            .enumerate()
+            .any(|(i, p)| singles[..i].contains(p))
+        {
+            return Err("a player can't play two singles positions");
+        }
+        match (doubles, pair.as_slice()) {
+            (true, [a, b]) if a != b => {}
+            (true, _) => return Err("two different players are needed for the doubles"),
+            (false, []) => {}
+            (false, _) => return Err("there's no doubles in the tie"),
+        }
+        Ok(Lineup {
+            singles,
+            doubles: pair,
+        })
+    }
+    /// the member of the roster of `team` that `input` refers to
+    fn member(&self, team: &Player, input: &str) -> Result<Player, &'static str> {
+        let Some(roster) = self.0.get(&team.name) else {
+            return Player::try_from(input);
+        };
+        let fitting = roster
+            .iter()
+            .filter(|member| member.fits(input))
+            .collect::<Vec<_>>();
+        // the one with exactly this name, if many fit
+        let exact = fitting
+            .iter()
+            .copied()
+            .filter(|member| member.name.eq_ignore_ascii_case(input.trim()))
+            .collect::<Vec<_>>();
+        match (fitting.as_slice(), exact.as_slice()) {
+            ([], _) => Err("no such player in the roster"),
+            ([member], _) | (_, [member]) => Ok((*member).clone()),
+            _ => Err("ambiguous player, type more of the name"),
+        }
+    }
+}
+
+/// a [`super::Duel`] of two teams made up of rubbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tie {
+    /// id of the [`super::Duel`]
+    pub id: MatchId,
+    pub home: Lineup,
+    pub away: Lineup,
+    /// outcomes of the rubbers played, in order: `true` if the home team won
+    pub results: Vec<bool>,
+}
+
+impl Tie {
+    /// the players of the `i`th rubber of `format`: (home, away)
+    pub fn rubber(&self, format: &TieFormat, i: usize) -> (String, String) {
+        let pair = |players: &[Player]| {
+            players
+                .iter()
+                .map(|player| player.name.clone())
+                .collect::<Vec<_>>()
+                .join(" & ")
+        };
+        match format.0[i] {
+            Rubber::Singles(home, away) => (
+                self.home.singles[home].name.clone(),
+                self.away.singles[away].name.clone(),
+            ),
+            Rubber::Doubles => (pair(&self.home.doubles), pair(&self.away.doubles)),
+        }
+    }
+    /// the number of rubbers won by the home and the away team
+    pub fn score(&self) -> (usize, usize) {
+        let home = self.results.iter().filter(|won| **won).count();
+        (home, self.results.len() - home)
+    }
+    /// `true` if the home team's won the tie, `None` if it's not decided yet
+    pub fn winner(&self, format: &TieFormat) -> Option<bool> {
+        match self.score() {
+            (home, _) if home >= format.to_win() => Some(true),
+            (_, away) if away >= format.to_win() => Some(false),
+            _ => None,
+        }
+    }
+    /// set the outcomes of the rubbers from `input`: `1` or `<` if the home team won it, `2` or `>` if the away team,
+    /// eg: `1 2 1 1` or `<><<`, till the tie's decided
+    pub fn with_results(self, format: &TieFormat, input: &str) -> Result<Self, &'static str> {
+        let mut tie = Self {
+            results: Vec::new(),
+            ..self
+        };
+        for c in input.chars().filter(|c| !c.is_whitespace()) {
+            if tie.winner(format).is_some() {
+                return Err("more results than rubbers played: the tie's already decided");
+            }
+            tie.results.push(match c {
+                '1' | '<' => true,
+                '2' | '>' => false,
+                _ => return Err("results of rubbers are '1'/'<' or '2'/'>'"),
+            });
+        }
+        if tie.winner(format).is_none() {
+            return Err("the tie isn't decided yet: more results are needed");
+        }
+        Ok(tie)
+    }
+}
+
+impl std::fmt::Display for Tie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (home, away) = self.score();
+        write!(f, "{home}-{away}")
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
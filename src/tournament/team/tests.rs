@@ -0,0 +1,90 @@
+use super::*;
+use crate::tournament::Branch;
+
+#[test]
+fn tie_format() {
+    let corbillon = "corbillon".parse::<TieFormat>().unwrap();
+    assert_eq!(
+        TieFormat(vec![
+            Rubber::Singles(0, 0),
+            Rubber::Singles(1, 1),
+            Rubber::Doubles,
+            Rubber::Singles(0, 1),
+            Rubber::Singles(1, 0),
+        ]),
+        corbillon
+    );
+    assert_eq!((2, 2), corbillon.positions());
+    assert_eq!(3, corbillon.to_win());
+    assert_eq!("X, Y / doubles pair", corbillon.lineup_hint(2, false));
+    let swaythling = "Swaythling".parse::<TieFormat>().unwrap();
+    assert_eq!((3, 3), swaythling.positions());
+    assert!(!swaythling.has_doubles());
+    assert_eq!(Ok(TieFormat(vec![Rubber::Singles(2, 0)])), "cx".parse());
+    assert!("AX,BY".parse::<TieFormat>().is_err(), "even");
+    assert!("AX,DX,BY".parse::<TieFormat>().is_err());
+}
+
+#[test]
+fn tie() {
+    let member = |name: &str| Player {
+        name: name.into(),
+        ..Default::default()
+    };
+    let team = |name: &str| Player {
+        name: name.into(),
+        ..Default::default()
+    };
+    let rosters = Rosters(HashMap::from([(
+        "Tunas".to_string(),
+        vec![member("Anna"), member("Bob"), member("Johanna")],
+    )]));
+    let format = "corbillon".parse::<TieFormat>().unwrap();
+
+    let home = rosters
+        .lineup(&team("Tunas"), "bob, johanna / anna, bob", 2, true)
+        .unwrap();
+    assert_eq!(vec![member("Bob"), member("Johanna")], home.singles);
+    // exactly Anna, though Johanna fits as well
+    assert_eq!(member("Anna"), home.doubles[0]);
+    assert!(rosters
+        .lineup(&team("Tunas"), "bob / anna, bob", 2, true)
+        .is_err());
+    assert!(rosters
+        .lineup(&team("Tunas"), "bob, bob / a, b", 2, true)
+        .is_err());
+    assert!(rosters
+        .lineup(&team("Tunas"), "bob, cecil / a, b", 2, true)
+        .is_err());
+    assert!(rosters
+        .lineup(&team("Tunas"), "bob, anna / bob, bob", 2, true)
+        .is_err());
+    assert!(rosters
+        .lineup(&team("Tunas"), "bob, anna", 2, true)
+        .is_err());
+    // no roster: anyone
+    let away = rosters
+        .lineup(&team("Sharks"), "Xavi, Yann / Xavi, Zoe", 2, true)
+        .unwrap();
+
+    let tie = Tie {
+        id: MatchId::new(Branch::Winner, 1, 1),
+        home,
+        away,
+        results: Vec::new(),
+    };
+    assert_eq!(
+        ("Anna & Bob".to_string(), "Xavi & Zoe".to_string()),
+        tie.rubber(&format, 2)
+    );
+    assert_eq!(
+        ("Bob".to_string(), "Yann".to_string()),
+        tie.rubber(&format, 3)
+    );
+    assert!(tie.clone().with_results(&format, "1 2 1").is_err());
+    assert!(tie.clone().with_results(&format, "1 1 1 2").is_err());
+    assert!(tie.clone().with_results(&format, "1 x").is_err());
+    let tie = tie.with_results(&format, "<>><>").unwrap();
+    assert_eq!(Some(false), tie.winner(&format));
+    assert_eq!("2-3", tie.to_string());
+}
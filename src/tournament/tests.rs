@@ -15,10 +15,11 @@ mod double_elimination {
                 .players_from_path("data.csv")
                 .unwrap()
         };
-        let test_eq = |xp_bs: (Players, Players, Players), tment: &Tournament<DE>| {
-            let exp_f = DE::new(xp_bs.0, xp_bs.1, xp_bs.2);
-            let tm = Tournament { format: exp_f };
-            assert_eq!(&tm, tment);
+        let test_eq = |xp_bs: (Players, Players, Players), round, tment: &Tournament<DE>| {
+            let mut exp_f = DE::new(xp_bs.0, xp_bs.1, xp_bs.2);
+            exp_f.round = round;
+            let tm = Tournament::new(exp_f);
+            assert_eq!(tm.format, tment.format);
         };
 
         let gen_bs = |wb: &[Player], lb: &[Player], kb: &[Player]| -> (Players, Players, Players) {
@@ -90,9 +91,9 @@ mod double_elimination {
             ),
         ];
 
-        for xp_bs in xp_bs {
-            test_eq(xp_bs, &tment);
-            tment.play_next_round(true);
+        for (round, xp_bs) in xp_bs.into_iter().enumerate() {
+            test_eq(xp_bs, round, &tment);
+            tment.play_next_round(true).unwrap();
         }
         assert!(tment.is_end());
     }
@@ -106,12 +107,14 @@ mod double_elimination {
         let team = |name: &&str| Player {
             name: name.to_string(),
             class: None,
+            nr: 0,
         };
         let teams = |teams: &[&str]| teams.iter().map(team).collect::<Vec<_>>();
-        let test_eq = |xp_bs: (Players, Players, Players), tment: &Tournament<DE>| {
-            let exp_f = DE::new(xp_bs.0, xp_bs.1, xp_bs.2);
-            let tm = Tournament { format: exp_f };
-            assert_eq!(&tm, tment);
+        let test_eq = |xp_bs: (Players, Players, Players), round, tment: &Tournament<DE>| {
+            let mut exp_f = DE::new(xp_bs.0, xp_bs.1, xp_bs.2);
+            exp_f.round = round;
+            let tm = Tournament::new(exp_f);
+            assert_eq!(tm.format, tment.format);
         };
 
         let gen_bs = |wb: &[&str], lb: &[&str], kb: &[&str]| -> (Players, Players, Players) {
@@ -162,12 +165,32 @@ mod double_elimination {
             ),
         ];
 
-        for xp_bs in xp_bs {
-            test_eq(xp_bs, &tment);
-            tment.play_next_round(true);
+        for (round, xp_bs) in xp_bs.into_iter().enumerate() {
+            test_eq(xp_bs, round, &tment);
+            tment.play_next_round(true).unwrap();
         }
         assert!(tment.is_end());
     }
+    #[test]
+    fn match_ids() {
+        let mut tment = Tournament::new(DE::default())
+            .players_from_path("football-teams.csv")
+            .unwrap();
+        while !tment.is_end() {
+            tment.play_next_round(true).unwrap();
+        }
+        let ids = tment
+            .log()
+            .iter()
+            .map(|duel| duel.id.to_string())
+            .collect::<Vec<_>>();
+        let expected = [
+            "W1-1", "W1-2", "W1-3", "W1-4", "W1-5", "W1-6", "W1-7", "W1-8", "L1-1", "L1-2", "L1-3",
+            "L1-4", "W2-1", "W2-2", "W2-3", "W2-4", "L2-1", "L2-2", "L2-3", "L2-4", "L3-1", "L3-2",
+            "W3-1", "W3-2", "L4-1", "L4-2", "L5-1", "W4-1", "L6-1", "GF",
+        ];
+        assert_eq!(ids, expected);
+    }
 }
 
 mod single_elimination {
@@ -181,17 +204,18 @@ mod single_elimination {
         let team = |name: &&str| Player {
             name: name.to_string(),
             class: None,
+            nr: 0,
         };
         let teams = |teams: &[&str]| teams.iter().map(team).collect::<Vec<_>>();
         let mut tment = Tournament::new(SE::default())
             .players_from_path("football-teams.csv")
             .unwrap();
 
-        let test_eq = |xp_bs: (Players, Players), tment: &Tournament<SE>| {
-            let exp_tm = Tournament {
-                format: SE::new(xp_bs.0, xp_bs.1),
-            };
-            assert_eq!(&exp_tm, tment);
+        let test_eq = |xp_bs: (Players, Players), round, tment: &Tournament<SE>| {
+            let mut exp_f = SE::new(xp_bs.0, xp_bs.1);
+            exp_f.round = round;
+            let exp_tm = Tournament::new(exp_f);
+            assert_eq!(exp_tm.format, tment.format);
         };
         let gen_bs = |wb: &[&str], kb: &[&str]| (Players(teams(wb)), Players(teams(kb)));
         let xp_bs = [
@@ -236,9 +260,9 @@ mod single_elimination {
             ),
         ];
 
-        for xp_bs in xp_bs {
-            test_eq(xp_bs, &tment);
-            tment.play_next_round(true);
+        for (round, xp_bs) in xp_bs.into_iter().enumerate() {
+            test_eq(xp_bs, round, &tment);
+            tment.play_next_round(true).unwrap();
         }
         assert!(tment.is_end());
     }
@@ -250,11 +274,11 @@ mod single_elimination {
                 .players_from_path("data.csv")
                 .unwrap()
         };
-        let test_eq = |xp_bs: (Players, Players), tment: &Tournament<SE>| {
-            let exp_tm = Tournament {
-                format: SE::new(xp_bs.0, xp_bs.1),
-            };
-            assert_eq!(&exp_tm, tment);
+        let test_eq = |xp_bs: (Players, Players), round, tment: &Tournament<SE>| {
+            let mut exp_f = SE::new(xp_bs.0, xp_bs.1);
+            exp_f.round = round;
+            let exp_tm = Tournament::new(exp_f);
+            assert_eq!(exp_tm.format, tment.format);
         };
 
         let gen_bs = |wb: &[Player], kb: &[Player]| -> (Players, Players) {
@@ -322,12 +346,84 @@ mod single_elimination {
             ),
         ];
 
-        for xp_bs in xp_bs {
-            test_eq(xp_bs, &tment);
-            tment.play_next_round(true);
+        for (round, xp_bs) in xp_bs.into_iter().enumerate() {
+            test_eq(xp_bs, round, &tment);
+            tment.play_next_round(true).unwrap();
         }
         assert!(tment.is_end());
     }
+    #[test]
+    fn match_ids() {
+        let mut tment = Tournament::new(SE::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        while !tment.is_end() {
+            tment.play_next_round(true).unwrap();
+        }
+        let ids = tment
+            .log()
+            .iter()
+            .map(|duel| duel.id.to_string())
+            .collect::<Vec<_>>();
+        // the special duel is a part of the first round
+        let expected = [
+            "R1-1", "R1-2", "R1-3", "R1-4", "R1-5", "R2-1", "R2-2", "3P", "R3-1",
+        ];
+        assert_eq!(ids, expected);
+
+        // the same ids whoever wins: they're known in advance
+        let mut tment = Tournament::new(SE::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        tment.play_out(true, |_| false).unwrap();
+        let ids = tment
+            .log()
+            .iter()
+            .map(|duel| duel.id.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, expected);
+    }
+    #[test]
+    fn three_players() {
+        let players = ["Anna", "Bob", "Cecil"].map(|name| nu_p(name, 9, 'A'));
+        let mut tment = Tournament::new(SE::default())
+            .with_players(Players(players.to_vec()))
+            .unwrap();
+        tment.play_out(true, |_| true).unwrap();
+        assert!(tment.is_end());
+        // no third place duel: there's only one semi-final loser
+        let ids = tment
+            .log()
+            .iter()
+            .map(|duel| duel.id.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["R1-1", "R2-1"]);
+        assert_eq!(3, tment.format.clone().results().0.len());
+    }
+    #[test]
+    fn third_place_standings() {
+        let players = ["Anna", "Bob", "Cecil", "Dora"].map(|name| nu_p(name, 9, 'A'));
+        let mut tment = Tournament::new(SE::default())
+            .with_players(Players(players.to_vec()))
+            .unwrap();
+        // the semi-finals
+        tment.play_next_stage_with(true, |_| true).unwrap();
+        let places = tment
+            .standings()
+            .iter()
+            .map(|standing| (standing.place, standing.note.clone()))
+            .collect::<Vec<_>>();
+        let (still_in, third) = ("still in".to_string(), "third place duel".to_string());
+        assert_eq!(
+            vec![
+                (1, still_in.clone()),
+                (1, still_in),
+                (3, third.clone()),
+                (3, third)
+            ],
+            places
+        );
+    }
 }
 
 mod round_robin {
@@ -364,9 +460,9 @@ mod round_robin {
             let exp_f = RR::new(players.clone(), xp.1.clone(), xp.2);
 
             assert_eq!(xp.0, tment.clone().format.gen_duels());
-            let xp_tm = Tournament { format: exp_f };
+            let xp_tm = Tournament::new(exp_f);
 
-            assert_eq!(&xp_tm, tment);
+            assert_eq!(xp_tm.format, tment.format);
         };
 
         let gen_xp = |duels: &[Duel],
@@ -435,7 +531,751 @@ mod round_robin {
         while !tment.is_end() {
             let xp = xps.next().unwrap();
             test_eq(xp, &tment);
-            tment.play_next_round(true);
+            tment.play_next_round(true).unwrap();
         }
     }
+
+    #[test]
+    fn results() {
+        // odd number of players: there's a ghost
+        let mut tment = Tournament::new(RR::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        tment.play_out(true, |_| fastrand::bool()).unwrap();
+        let mut results = tment.format.clone().results().0;
+        assert!(results.iter().all(|player| !player.is_unset()));
+        // best last
+        results.reverse();
+        let standings = tment
+            .standings()
+            .into_iter()
+            .map(|standing| standing.player)
+            .collect::<Vec<_>>();
+        assert_eq!(9, results.len());
+        assert_eq!(standings, results);
+    }
+
+    #[test]
+    fn crosstable() {
+        let mut tment = Tournament::new(RR::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        while !tment.is_end() {
+            tment.play_next_round(true).unwrap();
+        }
+        let table = tment.crosstable().unwrap();
+        // best first
+        assert_eq!(nu_p("Central Mite", 10, 'D'), table.players[0]);
+        assert_eq!((8, 8, 1), (table.wins[0], table.points[0], table.places[0]));
+        assert_eq!(table.points.iter().sum::<usize>(), 9 * 8 / 2);
+        assert!(table
+            .render()
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("1  Central Mite, 10D "));
+
+        let path = std::env::temp_dir().join("tuna-man-crosstable.md");
+        tment.save_crosstable(&path).unwrap();
+        let md = std::fs::read_to_string(&path).unwrap();
+        assert!(md.starts_with("| # | Player | 1 | 2 |"));
+        assert_eq!(11, md.lines().count());
+
+        let elimination = Tournament::new(format::SingleElimination::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        assert_eq!(None, elimination.crosstable());
+    }
+}
+
+mod html {
+    use super::*;
+
+    #[test]
+    fn elimination() {
+        let mut tment = Tournament::new(format::DoubleElimination::default())
+            .players_from_path("football-teams.csv")
+            .unwrap();
+        tment.play_next_round(true).unwrap();
+        let html = tment.html();
+        assert!(html.contains("<h2>Winner branch</h2>"));
+        assert!(html.contains("<h2>Loser branch</h2>"));
+        assert!(!html.contains("<h2>Grand final</h2>"));
+        assert!(html.contains("<div class=\"id\">W1-8</div>"));
+        assert!(html.contains("<div class=\"player won\">Germany</div>"));
+        assert!(html.contains("<td>1</td><td class=\"name\">Germany</td><td>winner branch</td>"));
+
+        while !tment.is_end() {
+            tment.play_next_round(true).unwrap();
+        }
+        let html = tment.html();
+        assert!(html.contains("<div class=\"id\">GF</div>"));
+        assert!(html.contains("<td>1</td><td class=\"name\">Germany</td><td>knocked out</td>"));
+    }
+
+    #[test]
+    fn round_robin() {
+        let mut tment = Tournament::new(format::RoundRobin::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        tment.play_next_round(true).unwrap();
+        let html = tment.html();
+        assert!(html.contains("<h2>Crosstable</h2>"));
+        assert!(!html.contains("waiting for player"));
+        assert!(html
+            .contains("<td>1</td><td class=\"name\">Relative Wrasse, 10C</td><td>1 points</td>"));
+        assert!(!html.contains("<h2>Class cup</h2>"));
+    }
+
+    #[test]
+    fn class_cup() {
+        let mut tment = Tournament::new(format::RoundRobin::default())
+            .players_from_path("data.csv")
+            .unwrap()
+            .with_class_cup(class_cup::Points::default());
+        tment.play_out(true, |_| true).unwrap();
+        let cup = tment.class_cup().unwrap();
+        let standings = tment.standings();
+        // every player of data.csv has got a class of their own
+        assert_eq!(standings.len(), cup.scores.len());
+        // shared places are split, so every place is worth it's points once
+        let points = (1..=standings.len())
+            .map(|place| class_cup::Points::default().of(place))
+            .sum::<usize>();
+        let sum = cup.scores.iter().map(|score| score.points).sum::<f64>();
+        assert!((points as f64 - sum).abs() < 1e-9);
+        let html = tment.html();
+        assert!(html.contains("<h2>Class cup</h2>"));
+        let first = &cup.scores[0];
+        assert!(html.contains(&format!(
+            "<tr><td>1</td><td class=\"name\">{}</td><td>{}</td>",
+            first.class, first.points
+        )));
+    }
+}
+
+mod svg {
+    use super::*;
+
+    #[test]
+    fn double_elimination() {
+        let mut tment = Tournament::new(format::DoubleElimination::default())
+            .players_from_path("football-teams.csv")
+            .unwrap();
+        let empty = tment.svg(false);
+        assert!(empty.contains("width=\"210mm\" height=\"297mm\""));
+        assert!(empty.contains(">Winner branch</text>"));
+        assert!(empty.contains(">Loser branch</text>"));
+        assert!(empty.contains(">Grand final</text>"));
+        assert!(empty.contains(">Germany</text>"));
+        assert!(empty.contains(">Loser of W1-1</text>"));
+        assert!(empty.contains(">Winner of W1-1</text>"));
+        assert!(!empty.contains("font-weight=\"bold\">Germany"));
+
+        tment.play_next_round(true).unwrap();
+        let filled = tment.svg(true);
+        assert!(filled.contains("font-weight=\"bold\">Germany</text>"));
+        assert!(!filled.contains(">Winner of W1-1</text>"));
+        assert!(filled.contains(">Winner of W2-1</text>"));
+        assert_eq!(filled, tment.clone().svg(true));
+        // nothing's changed by making the svg
+        assert_eq!(tment.duels(), tment.clone().duels());
+    }
+
+    #[test]
+    fn landscape() {
+        let tment = Tournament::new(format::SingleElimination::default())
+            .players_from_path("data.csv")
+            .unwrap()
+            .with_svg("unused.svg", crate::tournament::svg::Paper::A3, true);
+        let svg = tment.svg(false);
+        assert!(svg.contains("width=\"420mm\" height=\"297mm\""));
+        assert!(svg.contains(">Third place</text>"));
+        assert!(svg.contains(">Loser of R2-1</text>"));
+    }
+}
+
+mod swiss_system {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tment() {
+        let players = players::tests::load_players();
+        let mut tment = Tournament::new(format::SwissSystem::default())
+            .with_players(players.clone())
+            .unwrap();
+
+        // first round: the top half against the bottom half, the last one gets the bye
+        let duels = tment.format.gen_duels();
+        let pairs = duels
+            .iter()
+            .rev()
+            .map(|duel| (duel.homie.name.as_str(), duel.guest.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("Central Mite", "Usable Bengal"),
+                ("Relative Wrasse", "Inviting Pheasant"),
+                ("Exotic Skunk", "Profound Ponytail"),
+                ("Droll Jaguar", "Expectant Wolfhound"),
+                ("Casual Ptarmigan", ""),
+            ],
+            pairs
+        );
+
+        let mut rounds = 0;
+        while !tment.is_end() {
+            tment.play_next_round(true).unwrap();
+            rounds += 1;
+        }
+        // 9 players: 4 rounds
+        assert_eq!(4, rounds);
+        assert_eq!(4 * 4, tment.log().len());
+        assert_eq!(4, tment.matches().len() - tment.log().len());
+
+        // no rematches
+        for (i, a) in tment.log().iter().enumerate() {
+            for b in &tment.log()[i + 1..] {
+                let same = (a.homie == b.homie && a.guest == b.guest)
+                    || (a.homie == b.guest && a.guest == b.homie);
+                assert!(!same, "{a} and {b}");
+            }
+        }
+        let standings = tment.format.standings();
+        assert_eq!(players.0[0], standings[0].player);
+        assert_eq!("4 points, 9 buchholz", standings[0].note);
+        let results = tment.format.results();
+        assert_eq!(Some(&players.0[0]), results.0.last());
+        assert_eq!(9, results.0.len());
+    }
+
+    #[test]
+    fn seeded() {
+        let players = players::tests::load_players();
+        let tment = Tournament::new(format::SingleElimination::default())
+            .with_seeded_players(Players(players.0[..8].to_vec()))
+            .unwrap();
+        let mut expected = Players(players.0[..8].to_vec());
+        expected.seed();
+        assert_eq!(
+            format::SingleElimination::new(expected, Players::default()),
+            tment.format
+        );
+
+        // the swiss-system keeps the seed order
+        let tment = Tournament::new(format::SwissSystem::default())
+            .with_seeded_players(players.clone())
+            .unwrap();
+        assert_eq!(players, tment.format.players);
+    }
+}
+
+mod americano {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// the partners of the participants of every played duel
+    fn partners<F: format::Format>(tment: &Tournament<F>) -> Vec<Vec<Player>> {
+        tment
+            .log()
+            .iter()
+            .flat_map(|duel| [tment.members(&duel.homie), tment.members(&duel.guest)])
+            .collect()
+    }
+
+    #[test]
+    fn americano() {
+        let players = players::tests::load_players();
+        let mut tment = Tournament::new(format::Americano::default())
+            .with_players(Players(players.0[..8].to_vec()))
+            .unwrap();
+        tment.play_out(true, |_| true).unwrap();
+        // 8 players: 7 rounds on 2 courts
+        assert_eq!(7 * 2, tment.log().len());
+
+        // every player partners every other once
+        let mut partners = partners(&tment);
+        for pair in &mut partners {
+            assert_eq!(2, pair.len());
+            pair.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        partners.sort_by_key(|pair| pair.iter().map(|p| p.name.clone()).collect::<Vec<_>>());
+        partners.dedup();
+        assert_eq!(8 * 7 / 2, partners.len());
+
+        // a point for both winners of every duel
+        let standings = tment.standings();
+        assert_eq!(8, standings.len());
+        let points = tment
+            .format
+            .points
+            .values()
+            .map(|p| *p as usize)
+            .sum::<usize>();
+        assert_eq!(2 * tment.log().len(), points);
+    }
+
+    #[test]
+    fn mexicano() {
+        let players = players::tests::load_players();
+        let mut tment = Tournament::new(format::Americano::mexicano())
+            .with_players(players.clone())
+            .unwrap();
+        tment.play_next_round(true).unwrap();
+        // the homies won: the first and the fourth, the fifth and the eighth
+        let standings = tment.standings();
+        let winners = [0, 3, 4, 7].map(|i| players.0[i].clone());
+        let leaders = standings
+            .iter()
+            .filter(|standing| standing.place == 1)
+            .map(|standing| standing.player.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(winners.to_vec(), leaders);
+        assert_eq!("1 points", standings[0].note);
+
+        // the first and the fourth of the standings together
+        let duels = tment.format.gen_duels();
+        let first = tment.format.members(&duels[1].homie);
+        assert_eq!(vec![winners[0].clone(), winners[3].clone()], first);
+
+        tment.play_out(true, |_| true).unwrap();
+        // 9 players: 9 rounds, everyone sitting out once
+        assert_eq!(9 * 2, tment.log().len());
+        let partners = partners(&tment);
+        for player in &players.0 {
+            let played = partners.iter().filter(|pair| pair.contains(player)).count();
+            assert_eq!(8, played, "{player}");
+        }
+    }
+
+    /// `count` of the players of data.csv
+    fn first(count: usize) -> Players {
+        Players(players::tests::load_players().0[..count].to_vec())
+    }
+
+    #[test]
+    fn three_players() {
+        for format in [format::Americano::default(), format::Americano::mexicano()] {
+            let res = Tournament::new(format).with_players(first(3));
+            assert!(matches!(res, Err(TournamentError::InvalidCount(_))));
+        }
+    }
+
+    #[test]
+    fn five_players() {
+        let mut tment = Tournament::new(format::Americano::default())
+            .with_players(first(5))
+            .unwrap();
+        tment.play_out(true, |_| true).unwrap();
+        // 5 rounds on a court, everyone sitting out once
+        assert_eq!(5, tment.log().len());
+        let mut pairs = partners(&tment);
+        for pair in &mut pairs {
+            pair.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        pairs.sort_by_key(|pair| pair.iter().map(|p| p.name.clone()).collect::<Vec<_>>());
+        pairs.dedup();
+        assert_eq!(5 * 4 / 2, pairs.len());
+
+        let mut tment = Tournament::new(format::Americano::mexicano())
+            .with_players(first(5))
+            .unwrap();
+        tment.play_out(true, |_| true).unwrap();
+        assert_eq!(5, tment.log().len());
+        let pairs = partners(&tment);
+        for player in &first(5).0 {
+            let played = pairs.iter().filter(|pair| pair.contains(player)).count();
+            assert_eq!(4, played, "{player}");
+        }
+    }
+
+    #[test]
+    fn six_players() {
+        // 15 partnerships can't be played in duels of two of them
+        let res = Tournament::new(format::Americano::default()).with_players(first(6));
+        assert!(matches!(res, Err(TournamentError::InvalidCount(_))));
+
+        let mut tment = Tournament::new(format::Americano::mexicano())
+            .with_players(first(6))
+            .unwrap();
+        tment.play_out(true, |_| true).unwrap();
+        // 5 rounds on a court, 2 players sitting out every one of them
+        assert_eq!(5, tment.log().len());
+        let partners = partners(&tment);
+        for player in &first(6).0 {
+            let played = partners.iter().filter(|pair| pair.contains(player)).count();
+            assert!((3..=4).contains(&played), "{player}: {played}");
+        }
+    }
+}
+
+mod simulation {
+    use super::*;
+    use crate::tournament::simulation::Outcomes;
+
+    #[test]
+    fn uniform() {
+        for _ in 0..200 {
+            let mut tment = Tournament::new(format::DoubleElimination::default())
+                .players_from_path("football-teams.csv")
+                .unwrap();
+            let rounds = tment.simulate(true, &Outcomes::Uniform).unwrap();
+            assert!(tment.is_end());
+            assert!(rounds > 0);
+            assert!(tment.log().iter().all(|duel| duel.outcome.is_some()));
+            assert_eq!(16, tment.standings().len());
+        }
+    }
+
+    #[test]
+    fn rated() {
+        let outcomes = Outcomes::Rated([("Central Mite".to_string(), 3000.)].into());
+        let mut wins = 0;
+        for _ in 0..100 {
+            let mut tment = Tournament::new(format::SingleElimination::default())
+                .players_from_path("data.csv")
+                .unwrap();
+            tment.simulate(false, &outcomes).unwrap();
+            if tment.standings()[0].player.name == "Central Mite" {
+                wins += 1;
+            }
+        }
+        assert!(wins > 90, "{wins}");
+    }
+
+    #[test]
+    fn reproducible() {
+        let play = || {
+            fastrand::seed(7);
+            let mut tment = Tournament::new(format::SwissSystem::default())
+                .players_from_path("data.csv")
+                .unwrap();
+            tment.simulate(true, &Outcomes::Uniform).unwrap();
+            tment.log().to_vec()
+        };
+        assert_eq!(play(), play());
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn not_enough_players() {
+        let players = Players(vec![nu_p("Anna", 9, 'A'), nu_p("Bob", 9, 'B')]);
+        let tment = Tournament::new(format::RoundRobin::default()).with_players(players);
+        assert!(matches!(tment, Err(TournamentError::NotEnoughPlayers(2))));
+    }
+
+    #[test]
+    fn no_outcome() {
+        let duel = Duel::new(nu_p("Anna", 9, 'A'), nu_p("Bob", 9, 'B'));
+        assert!(matches!(
+            duel.into_result(),
+            Err(TournamentError::NoOutcome(_))
+        ));
+
+        let mut tment = Tournament::new(format::SingleElimination::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        let duels = tment.format.next_duels(false).unwrap();
+        let before = tment.format.clone();
+        let err = tment.format.apply(duels.clone()).unwrap_err();
+        assert_eq!("match R1-4 hasn't got an outcome yet", err.to_string());
+        assert_eq!(before, tment.format);
+        // the whole id has to match, not just the number
+        let other_round = duels
+            .iter()
+            .cloned()
+            .map(|duel| Duel {
+                id: MatchId {
+                    round: 2,
+                    ..duel.id
+                },
+                ..duel.with_outcome(Some(true))
+            })
+            .collect();
+        assert!(tment.format.apply(other_round).is_err());
+        assert_eq!(before, tment.format);
+        // not even the settled ones are taken
+        let mut partly = duels
+            .into_iter()
+            .map(|duel| duel.with_outcome(Some(true)))
+            .collect::<Vec<_>>();
+        partly.pop();
+        assert!(tment.format.apply(partly).is_err());
+        assert_eq!(before, tment.format);
+    }
+
+    #[test]
+    fn mixed_classes() {
+        let mut players = players::tests::load_players();
+        players.0.push(Player {
+            name: "Classless".into(),
+            class: None,
+            nr: 0,
+        });
+        players.shuffle_as_pairs();
+        assert_eq!(10, players.0.len());
+    }
+}
+
+mod late_entry {
+    use super::*;
+
+    #[test]
+    fn round_robin() {
+        let mut tment = Tournament::new(format::RoundRobin::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        let first = tment.format.players.0[0].clone();
+        tment.play_next_round(true).unwrap();
+        assert_eq!(0, tment.format.points[&first], "had a bye");
+
+        let late = tment.enter_late("Late Comer, 10A").unwrap();
+        // the bye of the first round is a walkover now
+        assert_eq!(1, tment.format.points[&first]);
+        assert_eq!(0, tment.format.points[&late]);
+        let walkover = tment.log().last().unwrap();
+        assert!(walkover.has(&first) && walkover.has(&late));
+        assert_eq!(Some(true), walkover.outcome);
+        assert_eq!(Branch::Round, walkover.id.branch);
+        assert_eq!(1, walkover.id.round);
+        assert!(tment.enter_late("Central Mite, 10D").is_err(), "taken");
+
+        tment.play_out(true, |_| true).unwrap();
+        let played = tment.log().iter().filter(|duel| duel.has(&late)).count();
+        assert_eq!(9, played, "walkover included");
+        assert_eq!(10 * 9 / 2, tment.log().len());
+    }
+
+    #[test]
+    fn round_robin_even() {
+        let mut players = players::tests::load_players();
+        players.0.pop();
+        let mut tment = Tournament::new(format::RoundRobin::default())
+            .with_players(players)
+            .unwrap();
+        tment.play_next_round(true).unwrap();
+        tment.play_next_round(true).unwrap();
+        let late = tment.enter_late("Late Comer").unwrap();
+        let later = tment.enter_late("Later Comer").unwrap();
+        assert_eq!(2 * 8 / 2, tment.log().len(), "no walkovers");
+
+        tment.play_out(true, |_| true).unwrap();
+        assert!(tment.is_end());
+        let log = tment.log();
+        // every pair once
+        assert_eq!(10 * 9 / 2, log.len());
+        for (i, duel) in log.iter().enumerate() {
+            assert!(!log[i + 1..]
+                .iter()
+                .any(|other| other.has(&duel.homie) && other.has(&duel.guest)));
+            // nobody plays twice in a round
+            assert!(!log[i + 1..]
+                .iter()
+                .any(|other| other.id.round == duel.id.round
+                    && (other.has(&duel.homie) || other.has(&duel.guest))));
+        }
+        assert!(log.iter().any(|duel| duel.has(&late) && duel.has(&later)));
+        assert_eq!(10, tment.standings().len());
+    }
+
+    #[test]
+    fn swiss_system() {
+        let mut tment = Tournament::new(format::SwissSystem::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        tment.play_next_round(true).unwrap();
+        let late = tment.enter_late("Late Comer").unwrap();
+        assert_eq!(
+            Some(&late),
+            tment.standings().last().map(|standing| &standing.player)
+        );
+
+        tment.play_out(true, |_| true).unwrap();
+        let played = tment.log().iter().filter(|duel| duel.has(&late)).count();
+        assert_eq!(3, played, "every round after entering, no bye");
+        assert!(tment.enter_late("Later Comer").is_err(), "ended");
+    }
+
+    #[test]
+    fn unsupported() {
+        let mut tment = Tournament::new(format::SingleElimination::default())
+            .players_from_path("data.csv")
+            .unwrap();
+        tment.play_next_round(true).unwrap();
+        assert!(matches!(
+            tment.enter_late("Late Comer"),
+            Err(TournamentError::LateEntry(_))
+        ));
+    }
+}
+
+mod ties {
+    use super::*;
+
+    #[test]
+    fn enter_tie() {
+        let teams = ["Tunas", "Sharks", "Rays", "Eels"]
+            .map(|name| Player {
+                name: name.into(),
+                ..Default::default()
+            })
+            .to_vec();
+        let mut tment = Tournament::new(format::SingleElimination::default())
+            .with_players(Players(teams))
+            .unwrap();
+        assert!(tment
+            .enter_tie("1", "a, b / a, b", "x, y / x, y", "1 1 1")
+            .is_err());
+
+        let mut tment = tment.with_ties("corbillon".parse().unwrap(), team::Rosters::default());
+        let duels = tment.format.next_duels(false).unwrap();
+        tment.start_stage(duels);
+        let duel = tment.duels()[0].clone();
+
+        assert!(tment
+            .enter_tie("9", "a, b / a, b", "x, y / x, y", "1 1 1")
+            .is_err());
+        assert!(tment
+            .enter_tie("1", "a / a, b", "x, y / x, y", "1 1 1")
+            .is_err());
+        assert!(tment
+            .enter_tie("1", "a, b / a, b", "x, y / x, y", "1 1")
+            .is_err());
+        let tie = tment
+            .enter_tie("1", "a, b / a, b", "x, y / x, y", "2 1 2 2")
+            .unwrap();
+        assert_eq!("1-3", tie.to_string());
+        assert_eq!(Some(false), tment.duels()[0].outcome);
+        assert!(tment.tie(duel.id).is_some());
+
+        tment.undo().unwrap();
+        assert_eq!(None, tment.duels()[0].outcome);
+        assert!(tment.tie(duel.id).is_none());
+
+        tment
+            .enter_tie("1", "a, b / a, b", "x, y / x, y", "2 1 2 2")
+            .unwrap();
+        tment.play_out(true, |_| true).unwrap();
+        let played = tment.log().iter().find(|played| played.id == duel.id);
+        assert_eq!(
+            Some(duel.guest),
+            played.unwrap().clone().into_result().ok().map(|r| r.0)
+        );
+        assert_eq!(1, tment.ties.len());
+    }
+}
+
+mod heats {
+    use super::*;
+    use crate::args::Args;
+    use clap::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn players(count: usize) -> Players {
+        Players((0..count).map(|i| nu_p(&format!("P{i}"), 9, 'A')).collect())
+    }
+
+    #[test]
+    fn heats() {
+        assert!(format::Heats::new(4, 4).is_err());
+        let mut tment = Tournament::new(format::Heats::new(4, 2).unwrap())
+            .with_players(players(10))
+            .unwrap();
+
+        // 10 players: 3, 3, 4, dealt back and forth
+        let duels = tment.format.next_duels(false).unwrap();
+        tment.start_stage(duels);
+        let heats = tment.format.heats();
+        let sizes = heats
+            .iter()
+            .map(|heat| heat.players.len())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![3, 3, 4], sizes);
+        assert_eq!(nu_p("P5", 9, 'A'), heats[0].players[1]);
+        // the duels of every pair of a heat share it's id
+        assert_eq!(3 + 3 + 6, tment.duels().len());
+        let first = heats[0].id;
+        let of_first = tment.duels().iter().filter(|duel| duel.id == first);
+        assert_eq!(3, of_first.count());
+        assert!(matches!(
+            tment.finish_stage(),
+            Err(TournamentError::NoOutcome(_))
+        ));
+
+        // homies win: the heats are ranked in the order of their players
+        tment.play_next_round(true).unwrap();
+        assert!(!tment.is_end());
+        let standings = tment.standings();
+        assert_eq!((1, 7), (standings[5].place, standings[6].place));
+        assert_eq!(10, standings[9].place);
+
+        // 6 players: 3, 3, then a final of 4
+        tment.play_out(true, |_| true).unwrap();
+        let rounds = tment.format.rounds();
+        assert_eq!(3, rounds.len());
+        assert_eq!(4, rounds[2][0].players.len());
+        let standings = tment.standings();
+        let places = standings.iter().map(|s| s.place).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 3, 4, 5, 5, 7, 7, 7, 10], places);
+        assert_eq!(nu_p("P0", 9, 'A'), standings[0].player);
+        assert_eq!(nu_p("P0", 9, 'A'), tment.format.clone().results().0[9]);
+    }
+
+    #[test]
+    fn prompt() {
+        let mut tment = Tournament::new(format::Heats::new(4, 1).unwrap())
+            .with_players(players(7))
+            .unwrap();
+        let duels = tment.format.next_duels(false).unwrap();
+        tment.start_stage(duels);
+        // P0, P3, P4 and P1, P2, P5, P6
+        assert!(tment.enter_ranking("3 1 2").is_err());
+        assert!(tment.enter_ranking("2 1 1").is_err());
+        let heat = tment.enter_ranking("R1-1 p4, p0").unwrap();
+        assert_eq!("R1-1: P4, 9A > P0, 9A > P3, 9A", heat.to_string());
+        assert!(tment
+            .duels()
+            .iter()
+            .filter(|duel| duel.id == heat.id)
+            .all(|duel| duel.outcome.is_some()));
+
+        // the whole heat is taken back
+        tment.undo().unwrap();
+        assert!(tment.duels().iter().all(|duel| duel.outcome.is_none()));
+
+        // the withdrawn player loses every duel of the heat, whatever the ranking
+        tment.withdraw("p3").unwrap();
+        tment.enter_ranking("1 2 1 3").unwrap();
+        tment.enter_ranking("2 1 2 3 4").unwrap();
+        assert!(tment.is_settled());
+        tment.enter_late("P7, 9A").unwrap();
+        tment.finish_stage().unwrap();
+        let ranking = &tment.format.rounds()[0][0].ranking;
+        let expected = ["P0", "P4", "P3"].map(|name| nu_p(name, 9, 'A'));
+        assert_eq!(expected.to_vec(), *ranking);
+
+        // the late entrant runs in the final
+        tment.format.next_duels(false);
+        let duels = tment.format.next_duels(false).unwrap();
+        tment.start_stage(duels);
+        let finalists = tment.format.heats()[0].players.clone();
+        assert!(finalists.contains(&nu_p("P7", 9, 'A')));
+        assert!(tment.enter_late("P8, 9A").is_err());
+    }
+
+    #[test]
+    fn args() {
+        let args = |extra: &[&str]| {
+            let base = ["tuna-man", "data.csv", "--heats", "4"];
+            Args::try_parse_from(base.iter().chain(extra))
+        };
+        assert!(args(&[]).is_ok());
+        assert!(args(&["--divisions", "column"]).is_err());
+        assert!(args(&["--doubles"]).is_err());
+        assert!(args(&["--draw-teams", "2"]).is_err());
+    }
 }